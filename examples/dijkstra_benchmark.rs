@@ -0,0 +1,30 @@
+// Compare dijkstra on the binary heap and on the radix heap over a random graph with 10^6 edges.
+// cargo run --release --example dijkstra_benchmark
+use algorithms_and_data_structures_rust::data_structures::graph::Graph;
+use std::time::Instant;
+use xorshift::XorShift;
+
+#[path = "../src/xorshift.rs"]
+mod xorshift;
+
+fn main() {
+    let (n, m) = (100_000, 1_000_000);
+    let mut rng = XorShift::default();
+
+    let mut g = Graph::new(n);
+    for _ in 0..m {
+        let src = rng.rand(n as u64) as usize;
+        let dst = rng.rand(n as u64) as usize;
+        g.add_edge(src, dst, rng.rand(1_000_000_000) as usize);
+    }
+
+    let now = Instant::now();
+    let binary = g.dijkstra(0);
+    println!("binary heap: {:?}", now.elapsed());
+
+    let now = Instant::now();
+    let radix = g.dijkstra_with_radix_heap(0);
+    println!("radix heap:  {:?}", now.elapsed());
+
+    assert_eq!(binary, radix);
+}
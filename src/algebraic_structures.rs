@@ -1,6 +1,7 @@
 pub mod algebraical;
 pub mod commutativity;
 pub mod group;
+pub mod idempotence;
 pub mod identity;
 pub mod invertibility;
 pub mod magma;
@@ -1,3 +1,70 @@
-use super::{invertibility::Invertibility, monoid::Monoid};
+use super::{
+    algebraical::Algebraical, commutativity::Commutativity, identity::Identity,
+    invertibility::Invertibility, magma::Magma, monoid::Monoid, semigroup::SemiGroup,
+};
 
 pub trait Group: Monoid + Invertibility {}
+
+pub struct AddGroup;
+
+impl Algebraical for AddGroup {
+    type S = i64;
+}
+
+impl Magma for AddGroup {
+    fn op(a: Self::S, b: Self::S) -> Self::S {
+        a + b
+    }
+}
+
+impl Identity for AddGroup {
+    fn id() -> Self::S {
+        0
+    }
+}
+
+impl Invertibility for AddGroup {
+    fn inverse(a: Self::S) -> Self::S {
+        -a
+    }
+}
+
+impl SemiGroup for AddGroup {}
+
+impl Monoid for AddGroup {}
+
+impl Group for AddGroup {}
+
+impl Commutativity for AddGroup {}
+
+pub struct XorGroup;
+
+impl Algebraical for XorGroup {
+    type S = usize;
+}
+
+impl Magma for XorGroup {
+    fn op(a: Self::S, b: Self::S) -> Self::S {
+        a ^ b
+    }
+}
+
+impl Identity for XorGroup {
+    fn id() -> Self::S {
+        0
+    }
+}
+
+impl Invertibility for XorGroup {
+    fn inverse(a: Self::S) -> Self::S {
+        a
+    }
+}
+
+impl SemiGroup for XorGroup {}
+
+impl Monoid for XorGroup {}
+
+impl Group for XorGroup {}
+
+impl Commutativity for XorGroup {}
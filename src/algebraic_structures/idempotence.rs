@@ -0,0 +1,4 @@
+use super::magma::Magma;
+
+// op(a, a) = a
+pub trait Idempotence: Magma {}
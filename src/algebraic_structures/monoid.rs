@@ -1,4 +1,7 @@
-use super::{algebraical::Algebraical, identity::Identity, magma::Magma, semigroup::SemiGroup};
+use super::{
+    algebraical::Algebraical, commutativity::Commutativity, idempotence::Idempotence,
+    identity::Identity, magma::Magma, semigroup::SemiGroup,
+};
 
 pub trait Monoid: SemiGroup + Identity {}
 
@@ -24,6 +27,10 @@ impl SemiGroup for MinMonoid {}
 
 impl Monoid for MinMonoid {}
 
+impl Commutativity for MinMonoid {}
+
+impl Idempotence for MinMonoid {}
+
 pub struct MaxMonoid;
 
 impl Algebraical for MaxMonoid {
@@ -46,6 +53,10 @@ impl SemiGroup for MaxMonoid {}
 
 impl Monoid for MaxMonoid {}
 
+impl Commutativity for MaxMonoid {}
+
+impl Idempotence for MaxMonoid {}
+
 pub struct AddMonoid;
 
 impl Algebraical for AddMonoid {
@@ -68,7 +79,38 @@ impl SemiGroup for AddMonoid {}
 
 impl Monoid for AddMonoid {}
 
-struct RightMonoid;
+impl Commutativity for AddMonoid {}
+
+pub struct GcdMonoid;
+
+impl Algebraical for GcdMonoid {
+    type S = usize;
+}
+
+impl Magma for GcdMonoid {
+    fn op(mut a: Self::S, mut b: Self::S) -> Self::S {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
+
+impl Identity for GcdMonoid {
+    fn id() -> Self::S {
+        0
+    }
+}
+
+impl SemiGroup for GcdMonoid {}
+
+impl Monoid for GcdMonoid {}
+
+impl Commutativity for GcdMonoid {}
+
+impl Idempotence for GcdMonoid {}
+
+pub struct RightMonoid;
 
 impl Algebraical for RightMonoid {
     type S = usize;
@@ -94,6 +136,8 @@ impl SemiGroup for RightMonoid {}
 
 impl Monoid for RightMonoid {}
 
+impl Idempotence for RightMonoid {}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Sum {
     value: usize,
@@ -5,13 +5,17 @@ pub mod binomial_coefficients_table;
 pub mod compress;
 pub mod diameter_of_tree;
 pub mod dijkstra;
+pub mod dynamic_connectivity;
 pub mod ford_fullkerson;
 pub mod grundy;
+pub mod inversion_count;
 pub mod is_correct_bracket;
 pub mod kruskal;
 pub mod lca;
 pub mod max_clique;
 pub mod max_independent;
+pub mod mo;
+pub mod rectangle_count;
 pub mod scc;
 pub mod num;
 pub mod rolling_hash;
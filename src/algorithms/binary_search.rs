@@ -1,6 +1,16 @@
+use std::ops::Range;
+
 pub trait BinarySearch<T> {
     fn lower_bound(&self, key: T) -> usize;
     fn upper_bound(&self, key: T) -> usize;
+
+    // [lower_bound(key), upper_bound(key))
+    fn equal_range(&self, key: T) -> Range<usize>
+    where
+        T: Clone,
+    {
+        self.lower_bound(key.clone())..self.upper_bound(key)
+    }
 }
 
 impl<T> BinarySearch<T> for [T]
@@ -36,9 +46,122 @@ where
     }
 }
 
+macro_rules! impl_integer_search {
+    ($meguru:ident, $partition_point:ident, $t:ty, $wide:ty) => {
+        // the boundary on the side of ok, where pred(ok) and !pred(ng) and pred is monotone between them
+        // ok may be greater than ng
+        pub fn $meguru<F>(mut ok: $t, mut ng: $t, pred: F) -> $t
+        where
+            F: Fn($t) -> bool,
+        {
+            while (ok as $wide).abs_diff(ng as $wide) > 1 {
+                let mid = ((ok as $wide + ng as $wide) / 2) as $t;
+                if pred(mid) {
+                    ok = mid;
+                } else {
+                    ng = mid;
+                }
+            }
+            ok
+        }
+
+        // the minimum x in [lo, hi) such that !pred(x), or hi if none,
+        // where pred is monotone (true, ..., false) on [lo, hi)
+        pub fn $partition_point<F>(lo: $t, hi: $t, pred: F) -> $t
+        where
+            F: Fn($t) -> bool,
+        {
+            assert!(lo <= hi);
+            if lo == hi || !pred(lo) {
+                return lo;
+            }
+            $meguru(lo, hi, |x| x == hi || pred(x)) + 1
+        }
+    };
+}
+
+impl_integer_search!(meguru_i64, partition_point_i64, i64, i128);
+impl_integer_search!(meguru_u64, partition_point_u64, u64, u128);
+
+// the boundary on the side of ok after the given number of bisections
+pub fn bisect_f64<F>(mut ok: f64, mut ng: f64, iterations: usize, pred: F) -> f64
+where
+    F: Fn(f64) -> bool,
+{
+    for _ in 0..iterations {
+        let mid = (ok + ng) / 2.0;
+        if pred(mid) {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
+// the boundary on the side of ok, bisecting until |ok - ng| <= eps
+pub fn bisect_f64_eps<F>(mut ok: f64, mut ng: f64, eps: f64, pred: F) -> f64
+where
+    F: Fn(f64) -> bool,
+{
+    assert!(eps > 0.0);
+    while (ok - ng).abs() > eps {
+        let mid = (ok + ng) / 2.0;
+        // the interval cannot be split anymore
+        if mid == ok || mid == ng {
+            break;
+        }
+        if pred(mid) {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
+// the minimum x in [lo, hi] of f, where f is strictly decreasing and then non-decreasing
+// (e.g. convex), by the binary search on f(x) > f(x + 1)
+pub fn ternary_search_i64<T, F>(lo: i64, hi: i64, f: F) -> i64
+where
+    T: PartialOrd,
+    F: Fn(i64) -> T,
+{
+    partition_point_i64(lo, hi, |x| f(x) > f(x + 1))
+}
+
+// the argmin in [lo, hi] of a unimodal function by the golden-section search
+pub fn golden_section_search<T, F>(mut lo: f64, mut hi: f64, iterations: usize, f: F) -> f64
+where
+    T: PartialOrd,
+    F: Fn(f64) -> T,
+{
+    let r = (5f64.sqrt() - 1.0) / 2.0;
+    let mut x1 = hi - r * (hi - lo);
+    let mut x2 = lo + r * (hi - lo);
+    let mut f1 = f(x1);
+    let mut f2 = f(x2);
+    for _ in 0..iterations {
+        if f1 < f2 {
+            hi = x2;
+            x2 = x1;
+            f2 = f1;
+            x1 = hi - r * (hi - lo);
+            f1 = f(x1);
+        } else {
+            lo = x1;
+            x1 = x2;
+            f1 = f2;
+            x2 = lo + r * (hi - lo);
+            f2 = f(x2);
+        }
+    }
+    (lo + hi) / 2.0
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::algorithms::binary_search::BinarySearch;
+    use super::*;
 
     #[test]
     fn it_works() {
@@ -55,4 +178,65 @@ mod tests {
         assert_eq!(vec![0, 0, 0, 1, 1, 3].upper_bound(3), 6);
         assert_eq!(vec![0, 0, 0, 1, 1, 3].upper_bound(10), 6);
     }
+
+    #[test]
+    fn equal_range_test() {
+        let a = [0, 0, 0, 1, 1, 3];
+        assert_eq!(a.equal_range(0), 0..3);
+        assert_eq!(a.equal_range(1), 3..5);
+        assert_eq!(a.equal_range(2), 5..5);
+        assert_eq!(a.equal_range(4), 6..6);
+    }
+
+    #[test]
+    fn integer_search_test() {
+        // the maximum x such that x * x <= 10^18
+        assert_eq!(
+            meguru_i64(0, 2_000_000_000, |x| x * x <= 1_000_000_000_000_000_000),
+            1_000_000_000
+        );
+        // ok on the right side
+        assert_eq!(meguru_i64(100, -100, |x| x >= -7), -7);
+        assert_eq!(meguru_i64(i64::MIN, i64::MAX, |x| x < 5), 4);
+        assert_eq!(meguru_u64(0, u64::MAX, |x| x <= u64::MAX - 3), u64::MAX - 3);
+
+        assert_eq!(partition_point_i64(-10, 10, |x| x < 3), 3);
+        assert_eq!(partition_point_i64(-10, 10, |_| true), 10);
+        assert_eq!(partition_point_i64(-10, 10, |_| false), -10);
+        assert_eq!(partition_point_i64(5, 5, |_| true), 5);
+        assert_eq!(partition_point_u64(0, 100, |x| x * x < 50), 8);
+        let a = [1, 2, 2, 4, 7];
+        for key in 0..9 {
+            assert_eq!(
+                partition_point_u64(0, a.len() as u64, |i| a[i as usize] < key) as usize,
+                a.lower_bound(key)
+            );
+        }
+    }
+
+    #[test]
+    fn float_search_test() {
+        let sqrt2 = bisect_f64(0.0, 2.0, 100, |x| x * x <= 2.0);
+        assert!((sqrt2 - 2f64.sqrt()).abs() < 1e-12);
+        let cbrt = bisect_f64_eps(10.0, 0.0, 1e-9, |x| x * x * x >= 5.0);
+        assert!((cbrt - 5f64.cbrt()).abs() < 1e-8);
+        assert!(cbrt * cbrt * cbrt >= 5.0);
+        // eps smaller than the precision of f64
+        let x = bisect_f64_eps(0.0, 1e10, 1e-300, |x| x <= 3.0);
+        assert_eq!(x, 3.0);
+    }
+
+    #[test]
+    fn ternary_search_test() {
+        assert_eq!(ternary_search_i64(-100, 100, |x| (x - 17) * (x - 17)), 17);
+        assert_eq!(ternary_search_i64(-100, 100, |x| (x + 300).abs()), -100);
+        assert_eq!(ternary_search_i64(-100, 100, |x| -x), 100);
+        // a plateau at the minimum
+        assert_eq!(ternary_search_i64(0, 10, |x| (x - 3).max(0).max(1 - x)), 1);
+
+        let x = golden_section_search(-10.0, 10.0, 100, |x| (x - 1.5) * (x - 1.5) + 2.0);
+        assert!((x - 1.5).abs() < 1e-6);
+        let x = golden_section_search(0.0, 3.0, 100, |x: f64| -x.sin());
+        assert!((x - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+    }
 }
@@ -1,90 +1,93 @@
-use std::{collections::HashMap, iter::FromIterator};
-
-pub struct Compress {
-    pub coordinate: Vec<usize>,
-    pub scale: Vec<isize>,
+// the neighboring values used for the margin of Compress
+pub trait Adjacent: Sized {
+    fn prev(&self) -> Option<Self>;
+    fn next(&self) -> Option<Self>;
 }
 
-impl Compress {
-    pub fn new(v: &Vec<isize>, margin: bool) -> Compress {
-        let vs = {
-            let mut vs = vec![];
-            if margin {
-                for &x in v {
-                    vs.push(x - 1);
-                    vs.push(x);
-                    vs.push(x + 1);
+macro_rules! impl_adjacent {
+    ($($t:ty),*) => {
+        $(
+            impl Adjacent for $t {
+                fn prev(&self) -> Option<Self> {
+                    self.checked_sub(1)
                 }
-            } else {
-                for &x in v {
-                    vs.push(x);
+
+                fn next(&self) -> Option<Self> {
+                    self.checked_add(1)
                 }
             }
-            vs.sort();
-            vs.dedup();
-            vs
-        };
+        )*
+    };
+}
 
-        let d = HashMap::<isize, usize>::from_iter(
-            vs.iter().enumerate().map(|(a, &b)| (b, a)),
-        );
+impl_adjacent!(i32, i64, i128, isize, u32, u64, u128, usize);
 
-        let coordinate = v.iter().map(|a| *d.get(a).unwrap()).collect();
+// Coordinate compression of the values of any number of slices
+// index_of, lower_bound in O(log n) by binary search over the sorted scale
+pub struct Compress<T> {
+    // coordinates[i][j] is the index of vs[i][j] in scale
+    pub coordinates: Vec<Vec<usize>>,
+    // the sorted distinct values
+    pub scale: Vec<T>,
+}
 
-        Compress {
-            coordinate,
-            scale: vs,
-        }
+impl<T> Compress<T>
+where
+    T: Ord + Clone,
+{
+    pub fn new(vs: &[&[T]]) -> Compress<T> {
+        let scale = vs.iter().flat_map(|v| v.iter().cloned()).collect();
+        Self::build(vs, scale)
     }
-}
 
-pub struct Compress2 {
-    pub coordinate1: Vec<usize>,
-    pub coordinate2: Vec<usize>,
-    pub scale: Vec<isize>,
-}
+    // also register x - 1 and x + 1 for every x
+    pub fn with_margin(vs: &[&[T]]) -> Compress<T>
+    where
+        T: Adjacent,
+    {
+        let scale = vs
+            .iter()
+            .flat_map(|v| v.iter())
+            .flat_map(|x| [x.prev(), Some(x.clone()), x.next()])
+            .flatten()
+            .collect();
+        Self::build(vs, scale)
+    }
 
-impl Compress2 {
-    pub fn new(v1: &Vec<isize>, v2: &Vec<isize>, margin: bool) -> Compress2 {
-        let vs = {
-            let mut vs = vec![];
-            if margin {
-                for &x in v1 {
-                    vs.push(x - 1);
-                    vs.push(x);
-                    vs.push(x + 1);
-                }
-                for &x in v2 {
-                    vs.push(x - 1);
-                    vs.push(x);
-                    vs.push(x + 1);
-                }
-            } else {
-                for &x in v1 {
-                    vs.push(x);
-                }
-                for &x in v2 {
-                    vs.push(x);
-                }
-            }
-            vs.sort();
-            vs.dedup();
-            vs
+    fn build(vs: &[&[T]], mut scale: Vec<T>) -> Compress<T> {
+        scale.sort();
+        scale.dedup();
+        let mut res = Compress {
+            coordinates: vec![],
+            scale,
         };
+        res.coordinates = vs
+            .iter()
+            .map(|v| v.iter().map(|x| res.index_of(x).unwrap()).collect())
+            .collect();
+        res
+    }
 
-        let d = HashMap::<isize, usize>::from_iter(
-            vs.iter().enumerate().map(|(a, &b)| (b, a)),
-        );
+    // the number of distinct values
+    pub fn len(&self) -> usize {
+        self.scale.len()
+    }
 
-        let coordinate1 = v1.iter().map(|a| *d.get(a).unwrap()).collect();
+    pub fn is_empty(&self) -> bool {
+        self.scale.is_empty()
+    }
 
-        let coordinate2 = v2.iter().map(|a| *d.get(a).unwrap()).collect();
+    pub fn index_of(&self, x: &T) -> Option<usize> {
+        self.scale.binary_search(x).ok()
+    }
 
-        Compress2 {
-            coordinate1,
-            coordinate2,
-            scale: vs,
-        }
+    // the number of values less than x
+    pub fn lower_bound(&self, x: &T) -> usize {
+        self.scale.partition_point(|y| y < x)
+    }
+
+    pub fn value_at(&self, i: usize) -> &T {
+        &self.scale[i]
     }
 }
 
@@ -95,46 +98,57 @@ mod tests {
     #[test]
     fn case1_1() {
         let v = vec![3, 3, 1, 8, 1];
-        let compress = Compress::new(&v, false);
-        let cordinate = compress.coordinate;
-        let scale = compress.scale;
-        assert_eq!(cordinate, vec![1, 1, 0, 2, 0]);
-        assert_eq!(scale, vec![1, 3, 8]);
+        let compress = Compress::new(&[&v]);
+        assert_eq!(compress.coordinates, vec![vec![1, 1, 0, 2, 0]]);
+        assert_eq!(compress.scale, vec![1, 3, 8]);
     }
 
     #[test]
     fn case1_2() {
         let v = vec![3, 3, 1, 8, 1];
-        let compress = Compress::new(&v, true);
-        let cordinate = compress.coordinate;
-        let scale = compress.scale;
-        assert_eq!(cordinate, vec![3, 3, 1, 6, 1]);
-        assert_eq!(scale, vec![0, 1, 2, 3, 4, 7, 8, 9]);
+        let compress = Compress::with_margin(&[&v]);
+        assert_eq!(compress.coordinates, vec![vec![3, 3, 1, 6, 1]]);
+        assert_eq!(compress.scale, vec![0, 1, 2, 3, 4, 7, 8, 9]);
     }
 
     #[test]
     fn case2_1() {
         let v1 = vec![3, 3, 1, 7, 1];
         let v2 = vec![0, 2, 1, 9, 2];
-        let compress = Compress2::new(&v1, &v2, false);
-        let cordinate1 = compress.coordinate1;
-        let cordinate2 = compress.coordinate2;
-        let scale = compress.scale;
-        assert_eq!(cordinate1, vec![3, 3, 1, 4, 1]);
-        assert_eq!(cordinate2, vec![0, 2, 1, 5, 2]);
-        assert_eq!(scale, vec![0, 1, 2, 3, 7, 9]);
+        let compress = Compress::new(&[&v1, &v2]);
+        assert_eq!(compress.coordinates[0], vec![3, 3, 1, 4, 1]);
+        assert_eq!(compress.coordinates[1], vec![0, 2, 1, 5, 2]);
+        assert_eq!(compress.scale, vec![0, 1, 2, 3, 7, 9]);
     }
 
     #[test]
     fn case2_2() {
         let v1 = vec![3, 3, 1, 7, 1];
         let v2 = vec![0, 2, -1, 9, 2];
-        let compress = Compress2::new(&v1, &v2, true);
-        let cordinate1 = compress.coordinate1;
-        let cordinate2 = compress.coordinate2;
-        let scale = compress.scale;
-        assert_eq!(cordinate1, vec![5, 5, 3, 8, 3]);
-        assert_eq!(cordinate2, vec![2, 4, 1, 10, 4]);
-        assert_eq!(scale, vec![-2, -1, 0, 1, 2, 3, 4, 6, 7, 8, 9, 10]);
+        let compress = Compress::with_margin(&[&v1, &v2]);
+        assert_eq!(compress.coordinates[0], vec![5, 5, 3, 8, 3]);
+        assert_eq!(compress.coordinates[1], vec![2, 4, 1, 10, 4]);
+        assert_eq!(compress.scale, vec![-2, -1, 0, 1, 2, 3, 4, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn lookup() {
+        let v1 = ["pear", "apple"];
+        let v2 = ["fig", "apple", "kiwi"];
+        let compress = Compress::new(&[&v1, &v2]);
+        assert_eq!(compress.len(), 4);
+        assert_eq!(compress.coordinates, vec![vec![3, 0], vec![1, 0, 2]]);
+        assert_eq!(compress.index_of(&"kiwi"), Some(2));
+        assert_eq!(compress.index_of(&"banana"), None);
+        assert_eq!(compress.lower_bound(&"banana"), 1);
+        assert_eq!(compress.lower_bound(&"zzz"), 4);
+        assert_eq!(compress.value_at(1), &"fig");
+
+        // the margin skips the values out of the range of the type
+        let v = vec![0usize, 5];
+        let compress = Compress::with_margin(&[&v]);
+        assert_eq!(compress.scale, vec![0, 1, 4, 5, 6]);
+        assert_eq!(compress.coordinates, vec![vec![0, 3]]);
+        assert!(Compress::<i32>::new(&[]).is_empty());
     }
 }
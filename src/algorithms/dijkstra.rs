@@ -1,4 +1,7 @@
-use crate::{chmin, data_structures::graph};
+use crate::{
+    chmin,
+    data_structures::{graph, radix_heap::RadixHeap},
+};
 use std::{cmp::Ordering, collections::BinaryHeap};
 const INF: usize = std::usize::MAX;
 
@@ -54,6 +57,26 @@ impl graph::Graph<usize> {
         }
         res
     }
+
+    // the same as dijkstra, running on a radix heap instead of a binary heap
+    pub fn dijkstra_with_radix_heap(&self, start: usize) -> Vec<usize> {
+        let mut res = vec![INF; self.size()];
+        res[start] = 0;
+        let mut que = RadixHeap::new();
+        que.push(0, start);
+
+        while let Some((priority, id)) = que.pop() {
+            if priority > res[id] {
+                continue;
+            }
+            for e in &self.edges[id] {
+                if chmin!(res[e.dst], res[e.src] + e.weight) {
+                    que.push(res[e.dst], e.dst);
+                }
+            }
+        }
+        res
+    }
 }
 
 #[cfg(test)]
@@ -73,5 +96,7 @@ mod tests {
         g.add_edge(4, 5, 3);
 
         assert_eq!(g.dijkstra(0), vec![0, 5, 3, 2, 3, 2, INF, INF, INF, INF]);
+        assert_eq!(g.dijkstra_with_radix_heap(0), g.dijkstra(0));
+        assert_eq!(g.dijkstra_with_radix_heap(2), g.dijkstra(2));
     }
 }
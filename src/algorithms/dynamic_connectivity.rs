@@ -0,0 +1,133 @@
+use crate::data_structures::rollback_union_find::RollbackUnionFind;
+use std::{collections::HashMap, mem};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Query {
+    AddEdge(usize, usize),
+    RemoveEdge(usize, usize),
+    IsSame(usize, usize),
+}
+
+// Answer every Query::IsSame(u, v) at the time it appears in queries (offline).
+// O(q log q log n)
+pub fn offline_dynamic_connectivity(n: usize, queries: &[Query]) -> Vec<bool> {
+    let q = queries.len();
+    if q == 0 {
+        return vec![];
+    }
+    let size = q.next_power_of_two();
+    // edges alive during the whole range of each node of the segment tree over time
+    let mut segment = vec![vec![]; 2 * size];
+
+    let mut add_range = |mut l: usize, mut r: usize, e: (usize, usize)| {
+        l += size;
+        r += size;
+        while l < r {
+            if l % 2 == 1 {
+                segment[l].push(e);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                segment[r].push(e);
+            }
+            l /= 2;
+            r /= 2;
+        }
+    };
+
+    let normalize = |u: usize, v: usize| if u < v { (u, v) } else { (v, u) };
+    let mut alive: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (t, &query) in queries.iter().enumerate() {
+        match query {
+            Query::AddEdge(u, v) => alive.entry(normalize(u, v)).or_default().push(t),
+            Query::RemoveEdge(u, v) => {
+                let e = normalize(u, v);
+                let start = alive
+                    .get_mut(&e)
+                    .and_then(|starts| starts.pop())
+                    .expect("removed an edge that does not exist");
+                add_range(start, t, e);
+            }
+            Query::IsSame(_, _) => {}
+        }
+    }
+    for (e, starts) in mem::take(&mut alive) {
+        for start in starts {
+            add_range(start, q, e);
+        }
+    }
+
+    let mut uf = RollbackUnionFind::new(n);
+    let mut res = vec![];
+    dfs(1, 0, size, queries, &segment, &mut uf, &mut res);
+    res
+}
+
+fn dfs(
+    idx: usize,
+    l: usize,
+    r: usize,
+    queries: &[Query],
+    segment: &[Vec<(usize, usize)>],
+    uf: &mut RollbackUnionFind,
+    res: &mut Vec<bool>,
+) {
+    if l >= queries.len() {
+        return;
+    }
+    let snapshot = uf.snapshot();
+    for &(u, v) in &segment[idx] {
+        uf.union(u, v);
+    }
+    if r - l == 1 {
+        if let Query::IsSame(u, v) = queries[l] {
+            res.push(uf.is_same(u, v));
+        }
+    } else {
+        let mid = (l + r) / 2;
+        dfs(2 * idx, l, mid, queries, segment, uf, res);
+        dfs(2 * idx + 1, mid, r, queries, segment, uf, res);
+    }
+    uf.rollback(snapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{offline_dynamic_connectivity, Query::*};
+
+    #[test]
+    fn it_works() {
+        let queries = vec![
+            AddEdge(0, 1),
+            AddEdge(1, 2),
+            IsSame(0, 2),
+            RemoveEdge(2, 1),
+            IsSame(0, 2),
+            IsSame(0, 1),
+            AddEdge(2, 3),
+            AddEdge(0, 3),
+            IsSame(1, 2),
+            RemoveEdge(0, 1),
+            IsSame(1, 2),
+            IsSame(0, 2),
+        ];
+        assert_eq!(
+            offline_dynamic_connectivity(4, &queries),
+            vec![true, false, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn multi_edge() {
+        let queries = vec![
+            AddEdge(0, 1),
+            AddEdge(1, 0),
+            RemoveEdge(0, 1),
+            IsSame(0, 1),
+            RemoveEdge(0, 1),
+            IsSame(0, 1),
+        ];
+        assert_eq!(offline_dynamic_connectivity(2, &queries), vec![true, false]);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{algebraic_structures::group::AddGroup, data_structures::fenwick_tree::FenwickTree};
+
+// the number of pairs (i, j) such that i < j and a[i] > a[j]
+// O(n log n)
+pub fn inversion_count<T>(a: &[T]) -> usize
+where
+    T: Ord,
+{
+    // rank[i]: position of a[i] after a stable sort
+    let rank = {
+        let mut idx = (0..a.len()).collect::<Vec<usize>>();
+        idx.sort_by(|&i, &j| a[i].cmp(&a[j]));
+        let mut rank = vec![0; a.len()];
+        for (r, i) in idx.into_iter().enumerate() {
+            rank[i] = r;
+        }
+        rank
+    };
+
+    let mut ft = FenwickTree::<AddGroup>::new(a.len());
+    let mut res = 0;
+    for (i, &r) in rank.iter().enumerate() {
+        res += i - ft.prefix_sum(r) as usize;
+        ft.add(r, 1);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inversion_count;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(inversion_count::<usize>(&[]), 0);
+        assert_eq!(inversion_count(&[1, 2, 3]), 0);
+        assert_eq!(inversion_count(&[3, 2, 1]), 3);
+        assert_eq!(inversion_count(&[3, 1, 3, 2, 1]), 6);
+        assert_eq!(inversion_count(&["b", "a", "c", "a"]), 3);
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{algorithms::lca::LCA, data_structures::graph::Graph};
+
+// the state of Mo's algorithm over a[l..r]
+pub trait MoState {
+    type Answer;
+    // a[i] enters from the left
+    fn add_left(&mut self, i: usize);
+    // a[i] enters from the right
+    fn add_right(&mut self, i: usize);
+    // a[i] leaves from the left
+    fn remove_left(&mut self, i: usize);
+    // a[i] leaves from the right
+    fn remove_right(&mut self, i: usize);
+    fn answer(&self) -> Self::Answer;
+}
+
+// the index of (x, y) along the Hilbert curve over [0, n) x [0, n), where n is a power of two
+fn hilbert_order(mut x: usize, mut y: usize, n: usize) -> usize {
+    let mut res = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = (x & s > 0) as usize;
+        let ry = (y & s > 0) as usize;
+        res += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    res
+}
+
+// the indices of the queries sorted along the Hilbert curve
+fn sorted_queries(n: usize, queries: &[(usize, usize)]) -> Vec<usize> {
+    let n = (n + 1).next_power_of_two();
+    let mut res = (0..queries.len()).collect::<Vec<_>>();
+    res.sort_by_cached_key(|&i| hilbert_order(queries[i].0, queries[i].1, n));
+    res
+}
+
+// move the current range [l, r) to [nl, nr)
+fn move_range<S>(state: &mut S, (l, r): &mut (usize, usize), nl: usize, nr: usize)
+where
+    S: MoState,
+{
+    while *l > nl {
+        *l -= 1;
+        state.add_left(*l);
+    }
+    while *r < nr {
+        state.add_right(*r);
+        *r += 1;
+    }
+    while *l < nl {
+        state.remove_left(*l);
+        *l += 1;
+    }
+    while *r > nr {
+        *r -= 1;
+        state.remove_right(*r);
+    }
+}
+
+// answer the queries [l, r) over a sequence of length n, O(n sqrt q) moves of the state
+pub fn mo<S>(n: usize, queries: &[(usize, usize)], state: &mut S) -> Vec<S::Answer>
+where
+    S: MoState,
+{
+    let mut res = (0..queries.len()).map(|_| None).collect::<Vec<_>>();
+    let mut cur = (0, 0);
+    for i in sorted_queries(n, queries) {
+        let (l, r) = queries[i];
+        assert!(l <= r && r <= n);
+        move_range(state, &mut cur, l, r);
+        res[i] = Some(state.answer());
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
+
+// the state of Mo's algorithm over the vertices on a path
+pub trait MoTreeState {
+    type Answer;
+    fn add(&mut self, v: usize);
+    fn remove(&mut self, v: usize);
+    fn answer(&self) -> Self::Answer;
+}
+
+// the vertices appearing an odd number of times in tour[l..r] are active
+struct EulerTourState<'a, S> {
+    state: &'a mut S,
+    tour: Vec<usize>,
+    active: Vec<bool>,
+}
+
+impl<S> EulerTourState<'_, S>
+where
+    S: MoTreeState,
+{
+    fn toggle(&mut self, i: usize) {
+        let v = self.tour[i];
+        self.active[v] = !self.active[v];
+        if self.active[v] {
+            self.state.add(v);
+        } else {
+            self.state.remove(v);
+        }
+    }
+}
+
+impl<S> MoState for EulerTourState<'_, S>
+where
+    S: MoTreeState,
+{
+    type Answer = S::Answer;
+
+    fn add_left(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn add_right(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn remove_left(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn remove_right(&mut self, i: usize) {
+        self.toggle(i);
+    }
+
+    fn answer(&self) -> Self::Answer {
+        self.state.answer()
+    }
+}
+
+// answer the queries over the vertices on the path between u and v (both inclusive) in the tree
+pub fn mo_on_tree<T, S>(
+    graph: &Graph<T>,
+    root: usize,
+    queries: &[(usize, usize)],
+    state: &mut S,
+) -> Vec<S::Answer>
+where
+    S: MoTreeState,
+{
+    let n = graph.edges.len();
+    let lca = LCA::new(n, root, graph);
+
+    // every vertex appears twice in the tour, at tin[v] and tout[v]
+    let mut tin = vec![0; n];
+    let mut tout = vec![0; n];
+    let mut tour = Vec::with_capacity(2 * n);
+    let mut visited = vec![false; n];
+    let mut stack = vec![(root, 0)];
+    visited[root] = true;
+    while let Some((v, i)) = stack.pop() {
+        if i == 0 {
+            tin[v] = tour.len();
+            tour.push(v);
+        }
+        if let Some(e) = graph.edges[v].get(i) {
+            stack.push((v, i + 1));
+            if !visited[e.dst] {
+                visited[e.dst] = true;
+                stack.push((e.dst, 0));
+            }
+        } else {
+            tout[v] = tour.len();
+            tour.push(v);
+        }
+    }
+
+    // the range of the tour, and the lca when it is not covered by the range
+    let ranges = queries
+        .iter()
+        .map(|&(u, v)| {
+            let (u, v) = if tin[u] <= tin[v] { (u, v) } else { (v, u) };
+            let w = lca.value(u, v);
+            if w == u {
+                ((tin[u], tin[v] + 1), None)
+            } else {
+                ((tout[u], tin[v] + 1), Some(w))
+            }
+        })
+        .collect::<Vec<_>>();
+    let tour_queries = ranges.iter().map(|&(range, _)| range).collect::<Vec<_>>();
+
+    let mut tour_state = EulerTourState {
+        state,
+        tour,
+        active: vec![false; n],
+    };
+    let mut res = (0..queries.len()).map(|_| None).collect::<Vec<_>>();
+    let mut cur = (0, 0);
+    for i in sorted_queries(2 * n, &tour_queries) {
+        let ((l, r), w) = ranges[i];
+        move_range(&mut tour_state, &mut cur, l, r);
+        res[i] = Some(match w {
+            Some(w) => {
+                tour_state.state.add(w);
+                let answer = tour_state.state.answer();
+                tour_state.state.remove(w);
+                answer
+            }
+            None => tour_state.state.answer(),
+        });
+    }
+    res.into_iter().map(|x| x.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mo, mo_on_tree, MoState, MoTreeState};
+    use crate::data_structures::graph::Graph;
+    use std::collections::HashSet;
+
+    // the number of distinct values
+    struct Distinct {
+        a: Vec<usize>,
+        count: Vec<usize>,
+        distinct: usize,
+    }
+
+    impl Distinct {
+        fn new(a: &[usize]) -> Self {
+            Distinct {
+                a: a.to_vec(),
+                count: vec![0; a.iter().max().map_or(0, |&x| x + 1)],
+                distinct: 0,
+            }
+        }
+
+        fn add(&mut self, x: usize) {
+            self.count[x] += 1;
+            if self.count[x] == 1 {
+                self.distinct += 1;
+            }
+        }
+
+        fn remove(&mut self, x: usize) {
+            self.count[x] -= 1;
+            if self.count[x] == 0 {
+                self.distinct -= 1;
+            }
+        }
+    }
+
+    impl MoState for Distinct {
+        type Answer = usize;
+
+        fn add_left(&mut self, i: usize) {
+            self.add(self.a[i]);
+        }
+
+        fn add_right(&mut self, i: usize) {
+            self.add(self.a[i]);
+        }
+
+        fn remove_left(&mut self, i: usize) {
+            self.remove(self.a[i]);
+        }
+
+        fn remove_right(&mut self, i: usize) {
+            self.remove(self.a[i]);
+        }
+
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    impl MoTreeState for Distinct {
+        type Answer = usize;
+
+        fn add(&mut self, v: usize) {
+            Distinct::add(self, self.a[v]);
+        }
+
+        fn remove(&mut self, v: usize) {
+            Distinct::remove(self, self.a[v]);
+        }
+
+        fn answer(&self) -> usize {
+            self.distinct
+        }
+    }
+
+    #[test]
+    fn mo_test() {
+        let a = vec![1, 2, 1, 3, 2, 2, 4, 1, 5, 3];
+        let queries = (0..=a.len())
+            .flat_map(|l| (l..=a.len()).map(move |r| (l, r)))
+            .collect::<Vec<_>>();
+        let res = mo(a.len(), &queries, &mut Distinct::new(&a));
+        for (&(l, r), &x) in queries.iter().zip(&res) {
+            assert_eq!(x, a[l..r].iter().collect::<HashSet<_>>().len());
+        }
+        assert!(mo(0, &[], &mut Distinct::new(&a)).is_empty());
+    }
+
+    #[test]
+    fn mo_on_tree_test() {
+        //        0
+        //      / | \
+        //     1  2  3
+        //    / \     \
+        //   4   5     6
+        //  /
+        // 7
+        let n = 8;
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (4, 7)];
+        let mut graph = Graph::new(n);
+        for &(a, b) in &edges {
+            graph.add_undirected_edge(a, b, 1);
+        }
+        let parent = [n, 0, 0, 0, 1, 1, 3, 4];
+        let path = |mut u: usize, mut v: usize| {
+            let depth = |mut v: usize| {
+                let mut d = 0;
+                while v != 0 {
+                    v = parent[v];
+                    d += 1;
+                }
+                d
+            };
+            let mut res = vec![];
+            while depth(u) > depth(v) {
+                res.push(u);
+                u = parent[u];
+            }
+            while depth(v) > depth(u) {
+                res.push(v);
+                v = parent[v];
+            }
+            while u != v {
+                res.push(u);
+                res.push(v);
+                u = parent[u];
+                v = parent[v];
+            }
+            res.push(u);
+            res
+        };
+
+        let colors = vec![1, 2, 1, 3, 2, 4, 3, 1];
+        let queries = (0..n)
+            .flat_map(|u| (0..n).map(move |v| (u, v)))
+            .collect::<Vec<_>>();
+        let res = mo_on_tree(&graph, 0, &queries, &mut Distinct::new(&colors));
+        for (&(u, v), &x) in queries.iter().zip(&res) {
+            let expected = path(u, v)
+                .iter()
+                .map(|&w| colors[w])
+                .collect::<HashSet<_>>()
+                .len();
+            assert_eq!(x, expected, "{} {}", u, v);
+        }
+    }
+}
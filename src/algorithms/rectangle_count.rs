@@ -0,0 +1,67 @@
+use crate::{
+    algebraic_structures::group::AddGroup, algorithms::compress::Compress,
+    data_structures::fenwick_tree::FenwickTree,
+};
+
+// For each query (x1, x2, y1, y2), count the points (x, y) such that x1 <= x < x2 and y1 <= y < y2.
+// O((n + q) log n)
+pub fn offline_rectangle_count(
+    points: &[(isize, isize)],
+    queries: &[(isize, isize, isize, isize)],
+) -> Vec<usize> {
+    let ys = points.iter().map(|&(_, y)| y).collect::<Vec<_>>();
+    let compress = Compress::new(&[&ys]);
+
+    let mut points = points.to_vec();
+    points.sort();
+
+    // (x, query index, sign): the number of points with x' < x is added with the sign
+    let mut events = Vec::with_capacity(2 * queries.len());
+    for (i, &(x1, x2, _, _)) in queries.iter().enumerate() {
+        if x1 >= x2 {
+            continue;
+        }
+        events.push((x1, i, -1));
+        events.push((x2, i, 1));
+    }
+    events.sort();
+
+    let mut ft = FenwickTree::<AddGroup>::new(compress.len());
+    let mut res = vec![0; queries.len()];
+    let mut idx = 0;
+    for (x, i, sign) in events {
+        while idx < points.len() && points[idx].0 < x {
+            ft.add(compress.lower_bound(&points[idx].1), 1);
+            idx += 1;
+        }
+        let (_, _, y1, y2) = queries[i];
+        let (l, r) = (compress.lower_bound(&y1), compress.lower_bound(&y2));
+        if l < r {
+            res[i] += sign * ft.sum(l..r);
+        }
+    }
+    res.into_iter().map(|x| x as usize).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::offline_rectangle_count;
+
+    #[test]
+    fn it_works() {
+        let points = vec![(0, 0), (2, 3), (2, 3), (-1, 5), (4, -2), (3, 3)];
+        let queries = vec![
+            (0, 3, 0, 4),
+            (-10, 10, -10, 10),
+            (2, 3, 3, 4),
+            (3, 2, 0, 10),
+            (-1, 0, 4, 6),
+            (0, 10, 4, 3),
+            (5, 10, -10, 10),
+        ];
+        assert_eq!(
+            offline_rectangle_count(&points, &queries),
+            vec![3, 6, 2, 0, 1, 0, 0]
+        );
+    }
+}
@@ -1,8 +1,31 @@
 pub mod argument;
+pub mod binary_trie;
+pub mod bit_vector;
+pub mod convex_hull_trick;
 pub mod counter;
+pub mod disjoint_sparse_table;
+pub mod dynamic_segment_tree;
+pub mod fenwick_multiset;
 pub mod fenwick_tree;
 pub mod graph;
+pub mod implicit_treap;
+pub mod interval_map;
 pub mod lazy_segment_tree;
+pub mod leftist_heap;
+pub mod li_chao_tree;
 pub mod mint;
+pub mod ordered_multiset;
+pub mod partially_persistent_union_find;
+pub mod persistent_segment_tree;
+pub mod radix_heap;
+pub mod range_tree;
+pub mod rollback_union_find;
 pub mod segment_tree;
+pub mod segment_tree_2d;
+pub mod segment_tree_beats;
+pub mod sliding_window_aggregation;
+pub mod sparse_table;
+pub mod sqrt_decomposition;
 pub mod union_find;
+pub mod wavelet_matrix;
+pub mod weighted_union_find;
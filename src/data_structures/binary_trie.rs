@@ -0,0 +1,224 @@
+#[derive(Clone, Copy)]
+struct Node {
+    // 0 means no child, since the root is never a child
+    children: [usize; 2],
+    // the number of elements in the subtree
+    count: usize,
+}
+
+// multiset of integers in [0, 2^bits) stored as a binary trie
+// every operation is O(bits)
+pub struct BinaryTrie {
+    bits: usize,
+    nodes: Vec<Node>,
+    // every element is regarded as xored with lazy
+    lazy: u64,
+}
+
+impl BinaryTrie {
+    pub fn new(bits: usize) -> BinaryTrie {
+        assert!(bits <= 64);
+        BinaryTrie {
+            bits,
+            nodes: vec![Node {
+                children: [0; 2],
+                count: 0,
+            }],
+            lazy: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn bit(&self, x: u64, d: usize) -> usize {
+        (x >> (self.bits - 1 - d) & 1) as usize
+    }
+
+    // xor every element with x
+    pub fn xor_all(&mut self, x: u64) {
+        assert!(self.bits == 64 || x >> self.bits == 0);
+        self.lazy ^= x;
+    }
+
+    pub fn insert(&mut self, x: u64) {
+        assert!(self.bits == 64 || x >> self.bits == 0);
+        let x = x ^ self.lazy;
+        let mut node = 0;
+        self.nodes[node].count += 1;
+        for d in 0..self.bits {
+            let b = self.bit(x, d);
+            if self.nodes[node].children[b] == 0 {
+                self.nodes.push(Node {
+                    children: [0; 2],
+                    count: 0,
+                });
+                self.nodes[node].children[b] = self.nodes.len() - 1;
+            }
+            node = self.nodes[node].children[b];
+            self.nodes[node].count += 1;
+        }
+    }
+
+    // remove one x, return false if x is not contained
+    pub fn erase(&mut self, x: u64) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        let x = x ^ self.lazy;
+        let mut node = 0;
+        self.nodes[node].count -= 1;
+        for d in 0..self.bits {
+            node = self.nodes[node].children[self.bit(x, d)];
+            self.nodes[node].count -= 1;
+        }
+        true
+    }
+
+    pub fn count(&self, x: u64) -> usize {
+        if self.bits < 64 && x >> self.bits != 0 {
+            return 0;
+        }
+        let x = x ^ self.lazy;
+        let mut node = 0;
+        for d in 0..self.bits {
+            node = self.nodes[node].children[self.bit(x, d)];
+            if node == 0 {
+                return 0;
+            }
+        }
+        self.nodes[node].count
+    }
+
+    // the k-th smallest (0-based) of {e ^ xor_mask | e in self}, where xor_mask < 2^bits
+    pub fn kth_smallest(&self, mut k: usize, xor_mask: u64) -> Option<u64> {
+        assert!(self.bits == 64 || xor_mask >> self.bits == 0);
+        if k >= self.len() {
+            return None;
+        }
+        let mask = xor_mask ^ self.lazy;
+        let mut node = 0;
+        let mut res = 0;
+        for d in 0..self.bits {
+            let b = self.bit(mask, d);
+            let child = self.nodes[node].children[b];
+            let count = if child == 0 {
+                0
+            } else {
+                self.nodes[child].count
+            };
+            res <<= 1;
+            if k < count {
+                node = child;
+            } else {
+                k -= count;
+                node = self.nodes[node].children[b ^ 1];
+                res |= 1;
+            }
+        }
+        Some(res)
+    }
+
+    // min {e ^ x | e in self}
+    pub fn min_xor(&self, x: u64) -> Option<u64> {
+        self.kth_smallest(0, x)
+    }
+
+    // max {e ^ x | e in self}
+    pub fn max_xor(&self, x: u64) -> Option<u64> {
+        self.len()
+            .checked_sub(1)
+            .and_then(|k| self.kth_smallest(k, x))
+    }
+
+    // the number of elements less than x
+    pub fn count_less(&self, x: u64) -> usize {
+        if self.bits < 64 && x >> self.bits != 0 {
+            return self.len();
+        }
+        let mut node = 0;
+        let mut res = 0;
+        for d in 0..self.bits {
+            let b = self.bit(x, d);
+            let l = self.bit(self.lazy, d);
+            // the child whose elements have the d-th bit 0 after xoring with lazy
+            if b == 1 {
+                let child = self.nodes[node].children[l];
+                if child != 0 {
+                    res += self.nodes[child].count;
+                }
+            }
+            node = self.nodes[node].children[b ^ l];
+            if node == 0 {
+                break;
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinaryTrie;
+
+    #[test]
+    fn it_works() {
+        let mut trie = BinaryTrie::new(4);
+        for x in [5, 3, 12, 3, 0] {
+            trie.insert(x);
+        }
+        assert_eq!(trie.len(), 5);
+        assert_eq!(trie.count(3), 2);
+        assert_eq!(trie.count(16), 0);
+        assert_eq!(trie.min_xor(6), Some(3));
+        assert_eq!(trie.max_xor(6), Some(10));
+        assert_eq!(trie.kth_smallest(2, 0), Some(3));
+        assert_eq!(trie.kth_smallest(4, 0), Some(12));
+        assert_eq!(trie.kth_smallest(5, 0), None);
+        assert_eq!(trie.count_less(3), 1);
+        assert_eq!(trie.count_less(4), 3);
+        assert_eq!(trie.count_less(16), 5);
+
+        assert!(trie.erase(3));
+        assert!(!trie.erase(7));
+        assert_eq!(trie.count(3), 1);
+
+        // {5, 3, 12, 0} -> {14, 8, 7, 11}
+        trie.xor_all(11);
+        assert_eq!(trie.kth_smallest(0, 0), Some(7));
+        assert_eq!(trie.kth_smallest(3, 0), Some(14));
+        assert_eq!(trie.count_less(11), 2);
+        assert_eq!(trie.count_less(12), 3);
+        assert_eq!(trie.count(8), 1);
+        assert_eq!(trie.count(3), 0);
+        trie.insert(1);
+        assert_eq!(trie.min_xor(0), Some(1));
+        assert!(trie.erase(14));
+        assert_eq!(trie.max_xor(0), Some(11));
+    }
+
+    #[test]
+    fn full_width() {
+        let mut trie = BinaryTrie::new(64);
+        trie.insert(u64::MAX);
+        trie.insert(1 << 63);
+        trie.insert(7);
+        assert_eq!(trie.max_xor(0), Some(u64::MAX));
+        assert_eq!(trie.min_xor(u64::MAX), Some(0));
+        assert_eq!(trie.count_less(1 << 63), 1);
+        assert_eq!(trie.kth_smallest(1, 0), Some(1 << 63));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mask_out_of_range() {
+        let mut trie = BinaryTrie::new(4);
+        trie.insert(5);
+        trie.min_xor(16);
+    }
+}
@@ -0,0 +1,131 @@
+// static bit vector with rank in O(1) and select in O(log n)
+pub struct BitVector {
+    len: usize,
+    // words[len / 64] always exists so that rank(len) needs no special case
+    words: Vec<u64>,
+    // the number of ones in words[0..i]
+    block_rank: Vec<usize>,
+}
+
+impl BitVector {
+    pub fn new(bits: &[bool]) -> BitVector {
+        let len = bits.len();
+        let mut words = vec![0u64; len / 64 + 1];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut block_rank = vec![0; words.len() + 1];
+        for (i, w) in words.iter().enumerate() {
+            block_rank[i + 1] = block_rank[i] + w.count_ones() as usize;
+        }
+        BitVector {
+            len,
+            words,
+            block_rank,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.block_rank[self.words.len()]
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    // the number of ones in [0, i)
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len);
+        let mask = (1u64 << (i % 64)) - 1;
+        self.block_rank[i / 64] + (self.words[i / 64] & mask).count_ones() as usize
+    }
+
+    // the number of zeros in [0, i)
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    // the position of the k-th one (0-based)
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        let w = self.block_rank.partition_point(|&r| r <= k) - 1;
+        let mut word = self.words[w];
+        for _ in 0..k - self.block_rank[w] {
+            word &= word - 1;
+        }
+        Some(64 * w + word.trailing_zeros() as usize)
+    }
+
+    // the position of the k-th zero (0-based)
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        if k >= self.count_zeros() {
+            return None;
+        }
+        // the number of zeros in words[0..w] is 64 * w - block_rank[w]
+        let (mut ok, mut ng) = (0, self.words.len());
+        while ng - ok > 1 {
+            let mid = (ok + ng) / 2;
+            if 64 * mid - self.block_rank[mid] <= k {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        let w = ok;
+        let mut word = !self.words[w];
+        for _ in 0..k - (64 * w - self.block_rank[w]) {
+            word &= word - 1;
+        }
+        Some(64 * w + word.trailing_zeros() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitVector;
+
+    #[test]
+    fn it_works() {
+        let bits = (0..200)
+            .map(|i: usize| (i * i + 3 * i) % 5 < 2)
+            .collect::<Vec<_>>();
+        let bv = BitVector::new(&bits);
+        assert_eq!(bv.len(), 200);
+        let ones = (0..200).filter(|&i| bits[i]).collect::<Vec<_>>();
+        let zeros = (0..200).filter(|&i| !bits[i]).collect::<Vec<_>>();
+        assert_eq!(bv.count_ones(), ones.len());
+        for i in 0..=200 {
+            assert_eq!(bv.rank1(i), bits[..i].iter().filter(|&&b| b).count());
+            assert_eq!(bv.rank0(i), bits[..i].iter().filter(|&&b| !b).count());
+        }
+        for k in 0..=200 {
+            assert_eq!(bv.select1(k), ones.get(k).copied());
+            assert_eq!(bv.select0(k), zeros.get(k).copied());
+        }
+        for (i, &b) in bits.iter().enumerate() {
+            assert_eq!(bv.get(i), b);
+        }
+
+        let bv = BitVector::new(&[true; 64]);
+        assert_eq!(bv.rank1(64), 64);
+        assert_eq!(bv.select1(63), Some(63));
+        assert_eq!(bv.select0(0), None);
+    }
+}
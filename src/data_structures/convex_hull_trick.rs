@@ -0,0 +1,459 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+pub trait LineNumber:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    // the type in which a * x + b and the differences of the coefficients are computed
+    // i128 for i64, and i128 itself since there is no wider primitive
+    type Wide: LineNumber;
+    const MIN: Self;
+    const MAX: Self;
+    fn widen(self) -> Self::Wide;
+    // panic if x does not fit in Self
+    fn narrow(x: Self::Wide) -> Self;
+    fn floor_div(a: Self, b: Self) -> Self;
+    // compare a * b with c * d without overflow
+    fn cmp_mul(a: Self, b: Self, c: Self, d: Self) -> Ordering;
+
+    fn eval_line(a: Self, b: Self, x: Self) -> Self::Wide {
+        a.widen() * x.widen() + b.widen()
+    }
+}
+
+impl LineNumber for i64 {
+    type Wide = i128;
+    const MIN: Self = i64::MIN;
+    const MAX: Self = i64::MAX;
+
+    fn widen(self) -> i128 {
+        self as i128
+    }
+
+    fn narrow(x: i128) -> Self {
+        i64::try_from(x).expect("the value overflows i64")
+    }
+
+    fn floor_div(a: Self, b: Self) -> Self {
+        a / b - (a % b != 0 && (a < 0) != (b < 0)) as i64
+    }
+
+    fn cmp_mul(a: Self, b: Self, c: Self, d: Self) -> Ordering {
+        (a as i128 * b as i128).cmp(&(c as i128 * d as i128))
+    }
+}
+
+// (high, low) of a * b
+fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = (1 << 64) - 1;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let low = a0 * b0;
+    let mid1 = a1 * b0;
+    let mid2 = a0 * b1;
+    let carry = ((low >> 64) + (mid1 & MASK) + (mid2 & MASK)) >> 64;
+    let high = a1 * b1 + (mid1 >> 64) + (mid2 >> 64) + carry;
+    (high, low.wrapping_add(mid1 << 64).wrapping_add(mid2 << 64))
+}
+
+impl LineNumber for i128 {
+    type Wide = i128;
+    const MIN: Self = i128::MIN;
+    const MAX: Self = i128::MAX;
+
+    fn widen(self) -> i128 {
+        self
+    }
+
+    fn narrow(x: i128) -> Self {
+        x
+    }
+
+    fn floor_div(a: Self, b: Self) -> Self {
+        a / b - (a % b != 0 && (a < 0) != (b < 0)) as i128
+    }
+
+    fn cmp_mul(a: Self, b: Self, c: Self, d: Self) -> Ordering {
+        let sign1 = a.signum() * b.signum();
+        let sign2 = c.signum() * d.signum();
+        if sign1 != sign2 || sign1 == 0 {
+            return sign1.cmp(&sign2);
+        }
+        let ord = mul_u128(a.unsigned_abs(), b.unsigned_abs())
+            .cmp(&mul_u128(c.unsigned_abs(), d.unsigned_abs()));
+        if sign1 > 0 {
+            ord
+        } else {
+            ord.reverse()
+        }
+    }
+}
+
+// Convex hull trick for lines added in monotone order of slope.
+// For minimize, slopes must be non-increasing; otherwise non-decreasing.
+// The lines are stored negated when maximizing, so that the hull is always a lower envelope.
+pub struct ConvexHullTrick<T> {
+    minimize: bool,
+    // (slope, intercept) in decreasing order of slope
+    lines: Vec<(T, T)>,
+    // the index of the line answered by the last query_monotone
+    cursor: usize,
+}
+
+impl<T> ConvexHullTrick<T>
+where
+    T: LineNumber,
+{
+    pub fn new(minimize: bool) -> Self {
+        ConvexHullTrick {
+            minimize,
+            lines: vec![],
+            cursor: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    // whether l2 is unnecessary for l1, l2, l3 with decreasing slopes
+    fn is_unnecessary(l1: (T, T), l2: (T, T), l3: (T, T)) -> bool {
+        let d = |a: T, b: T| a.widen() - b.widen();
+        T::Wide::cmp_mul(d(l3.1, l1.1), d(l1.0, l2.0), d(l2.1, l1.1), d(l1.0, l3.0))
+            != Ordering::Greater
+    }
+
+    fn eval(line: (T, T), x: T) -> T::Wide {
+        T::eval_line(line.0, line.1, x)
+    }
+
+    // add y = ax + b
+    pub fn add_line(&mut self, a: T, b: T) {
+        let line = if self.minimize { (a, b) } else { (-a, -b) };
+        if let Some(&last) = self.lines.last() {
+            assert!(line.0 <= last.0, "slopes must be monotone");
+            if line.0 == last.0 {
+                if line.1 >= last.1 {
+                    return;
+                }
+                self.lines.pop();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if Self::is_unnecessary(self.lines[n - 2], self.lines[n - 1], line) {
+                self.lines.pop();
+            } else {
+                break;
+            }
+        }
+        self.lines.push(line);
+    }
+
+    fn result(&self, y: T::Wide) -> T {
+        T::narrow(if self.minimize { y } else { -y })
+    }
+
+    // min (or max) of the lines at x, O(log n)
+    pub fn query(&self, x: T) -> Option<T> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let (mut ok, mut ng) = (0, self.lines.len());
+        while ng - ok > 1 {
+            let mid = (ok + ng) / 2;
+            if Self::eval(self.lines[mid - 1], x) > Self::eval(self.lines[mid], x) {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        Some(self.result(Self::eval(self.lines[ok], x)))
+    }
+
+    // the same as query, amortized O(1) when x is monotone over the calls
+    // The lines are kept, so any x is still answered correctly.
+    pub fn query_monotone(&mut self, x: T) -> Option<T> {
+        let eval = |i: usize| Self::eval(self.lines[i], x);
+        let mut i = self.cursor.min(self.lines.len().checked_sub(1)?);
+        while i > 0 && eval(i - 1) < eval(i) {
+            i -= 1;
+        }
+        while i + 1 < self.lines.len() && eval(i) >= eval(i + 1) {
+            i += 1;
+        }
+        self.cursor = i;
+        Some(self.result(eval(i)))
+    }
+}
+
+// Lines can be added in any order, O(log n) for each operation.
+// Internally keeps the upper envelope, and the lines are stored negated when minimizing.
+pub struct LineContainer<T> {
+    minimize: bool,
+    // slope -> (intercept, p) where p is the last x at which the line is the maximum
+    lines: BTreeMap<T, (T, T)>,
+    // (p, slope)
+    ps: BTreeSet<(T, T)>,
+}
+
+impl<T> LineContainer<T>
+where
+    T: LineNumber,
+{
+    pub fn new(minimize: bool) -> Self {
+        LineContainer {
+            minimize,
+            lines: BTreeMap::new(),
+            ps: BTreeSet::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    fn next(&self, k: T) -> Option<T> {
+        self.lines
+            .range((std::ops::Bound::Excluded(k), std::ops::Bound::Unbounded))
+            .next()
+            .map(|(&k, _)| k)
+    }
+
+    fn prev(&self, k: T) -> Option<T> {
+        self.lines.range(..k).next_back().map(|(&k, _)| k)
+    }
+
+    fn p(&self, k: T) -> T {
+        self.lines[&k].1
+    }
+
+    fn set_p(&mut self, k: T, p: T) {
+        let line = self.lines.get_mut(&k).unwrap();
+        self.ps.remove(&(line.1, k));
+        line.1 = p;
+        self.ps.insert((p, k));
+    }
+
+    fn erase(&mut self, k: T) {
+        let (_, p) = self.lines.remove(&k).unwrap();
+        self.ps.remove(&(p, k));
+    }
+
+    // update p of x with its next line y, and return whether y makes x unnecessary
+    fn intersect(&mut self, x: T, y: Option<T>) -> bool {
+        match y {
+            None => {
+                self.set_p(x, T::MAX);
+                false
+            }
+            Some(y) => {
+                let (mx, my) = (self.lines[&x].0, self.lines[&y].0);
+                let p = T::Wide::floor_div(my.widen() - mx.widen(), x.widen() - y.widen());
+                // the intersections beyond the range of T are never queried
+                let p = T::narrow(p.clamp(T::MIN.widen(), T::MAX.widen()));
+                self.set_p(x, p);
+                p >= self.p(y)
+            }
+        }
+    }
+
+    // add y = ax + b
+    pub fn add_line(&mut self, a: T, b: T) {
+        let (k, m) = if self.minimize { (-a, -b) } else { (a, b) };
+        if let Some(&(old, _)) = self.lines.get(&k) {
+            if old >= m {
+                return;
+            }
+            self.erase(k);
+        }
+        self.lines.insert(k, (m, T::MIN));
+        self.ps.insert((T::MIN, k));
+
+        let y = k;
+        while self.intersect(y, self.next(y)) {
+            let z = self.next(y).unwrap();
+            self.erase(z);
+        }
+        let mut x = match self.prev(y) {
+            Some(x) => x,
+            None => return,
+        };
+        if self.intersect(x, Some(y)) {
+            self.erase(y);
+            let z = self.next(x);
+            self.intersect(x, z);
+        }
+        let mut y = x;
+        while let Some(prev) = self.prev(y) {
+            x = prev;
+            if self.p(x) < self.p(y) {
+                break;
+            }
+            self.erase(y);
+            let z = self.next(x);
+            self.intersect(x, z);
+            y = x;
+        }
+    }
+
+    // min (or max) of the lines at x
+    pub fn query(&self, x: T) -> Option<T> {
+        let &(_, k) = self.ps.range((x, T::MIN)..).next()?;
+        let y = T::eval_line(k, self.lines[&k].0, x);
+        Some(T::narrow(if self.minimize { -y } else { y }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConvexHullTrick, LineContainer, LineNumber};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn line_number_test() {
+        assert_eq!(i64::floor_div(7, 2), 3);
+        assert_eq!(i64::floor_div(-7, 2), -4);
+        assert_eq!(i64::floor_div(7, -2), -4);
+        assert_eq!(i64::floor_div(-6, 3), -2);
+        assert_eq!(i128::floor_div(-7, -2), 3);
+
+        assert_eq!(i64::cmp_mul(i64::MAX, 2, i64::MAX, 3), Ordering::Less);
+        let big = i128::MAX / 3;
+        assert_eq!(i128::cmp_mul(big, 6, big, 5), Ordering::Greater);
+        assert_eq!(i128::cmp_mul(-big, 6, big, 5), Ordering::Less);
+        assert_eq!(i128::cmp_mul(-big, -6, 6, big), Ordering::Equal);
+        assert_eq!(i128::cmp_mul(-big, 6, -big, 5), Ordering::Less);
+        assert_eq!(i128::cmp_mul(0, 6, -1, 5), Ordering::Greater);
+    }
+
+    fn lines() -> Vec<(i64, i64)> {
+        vec![
+            (3, -5),
+            (2, 0),
+            (2, 3),
+            (1, -1),
+            (0, 4),
+            (-1, 2),
+            (-3, 10),
+            (-3, 9),
+        ]
+    }
+
+    #[test]
+    fn convex_hull_trick_test() {
+        let mut min = ConvexHullTrick::new(true);
+        let mut max = ConvexHullTrick::new(false);
+        assert_eq!(min.query(0), None);
+        for &(a, b) in &lines() {
+            min.add_line(a, b);
+        }
+        for &(a, b) in lines().iter().rev() {
+            max.add_line(a, b);
+        }
+        for x in -10..=10 {
+            let values = lines().iter().map(|&(a, b)| a * x + b).collect::<Vec<_>>();
+            assert_eq!(min.query(x), values.iter().min().copied());
+            assert_eq!(max.query(x), values.iter().max().copied());
+        }
+        for x in -10..=10 {
+            let values = lines().iter().map(|&(a, b)| a * x + b).collect::<Vec<_>>();
+            assert_eq!(min.query_monotone(x), values.iter().min().copied());
+            assert_eq!(max.query_monotone(x), values.iter().max().copied());
+        }
+    }
+
+    #[test]
+    fn query_monotone_test() {
+        let mut min = ConvexHullTrick::new(true);
+        for &(a, b) in &lines() {
+            min.add_line(a, b);
+        }
+        let naive = |x: i64| lines().iter().map(|&(a, b)| a * x + b).min();
+        for x in (-10..=10).chain((-10..=10).rev()) {
+            assert_eq!(min.query_monotone(x), naive(x));
+            assert_eq!(min.query(-x), naive(-x));
+        }
+
+        // lines added between the queries
+        let mut max = ConvexHullTrick::new(false);
+        let mut added = vec![];
+        for (x, &(a, b)) in (-8..).step_by(2).zip(lines().iter().rev()) {
+            max.add_line(a, b);
+            added.push((a, b));
+            let naive = |x: i64| added.iter().map(|&(a, b)| a * x + b).max();
+            assert_eq!(max.query_monotone(x), naive(x));
+            assert_eq!(max.query_monotone(x + 1), naive(x + 1));
+            assert_eq!(max.query(x - 5), naive(x - 5));
+        }
+    }
+
+    #[test]
+    fn line_container_test() {
+        let mut min = LineContainer::new(true);
+        let mut max = LineContainer::new(false);
+        let mut added = vec![];
+        let order = [4, 0, 7, 2, 5, 1, 6, 3];
+        for &i in &order {
+            let (a, b) = lines()[i];
+            min.add_line(a, b);
+            max.add_line(a, b);
+            added.push((a, b));
+            for x in -10..=10 {
+                let values = added.iter().map(|&(a, b)| a * x + b).collect::<Vec<_>>();
+                assert_eq!(min.query(x), values.iter().min().copied());
+                assert_eq!(max.query(x), values.iter().max().copied());
+            }
+        }
+        assert!(min.len() <= order.len());
+
+        let mut big = LineContainer::<i128>::new(true);
+        big.add_line(1_000_000_000_000_000_000, 0);
+        big.add_line(-1_000_000_000_000_000_000, 0);
+        big.add_line(0, -5);
+        assert_eq!(
+            big.query(1_000_000_000),
+            Some(-1_000_000_000_000_000_000_000_000_000)
+        );
+        assert_eq!(big.query(0), Some(-5));
+    }
+
+    #[test]
+    fn overflow_test() {
+        // the intercepts differ by 10^19 and a * x overflows i64 for the lines far from the optimum
+        const C: i64 = 5_000_000_000_000_000_000;
+        let lines = [(1, -C), (0, 0), (-1, C), (-1_000_000_000, C)];
+        let mut cht = ConvexHullTrick::new(true);
+        let mut container = LineContainer::new(true);
+        for &(a, b) in &lines {
+            cht.add_line(a, b);
+            container.add_line(a, b);
+        }
+        for x in [
+            -4_000_000_000_000_000_000,
+            -1_000_000_000,
+            0,
+            1,
+            9_000_000_000,
+        ] {
+            let expected = lines
+                .iter()
+                .map(|&(a, b)| a as i128 * x as i128 + b as i128)
+                .min()
+                .unwrap() as i64;
+            assert_eq!(cht.query(x), Some(expected));
+            assert_eq!(container.query(x), Some(expected));
+        }
+    }
+}
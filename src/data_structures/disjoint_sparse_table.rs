@@ -0,0 +1,112 @@
+use crate::algebraic_structures::semigroup::SemiGroup;
+
+// static range fold in O(1) for any semigroup, built in O(n log n)
+pub struct DisjointSparseTable<S>
+where
+    S: SemiGroup,
+{
+    len: usize,
+    // In level k, the array is divided into blocks of size 2^(k + 1), and for each block
+    // [s, s + 2^(k + 1)) with the middle m = s + 2^k,
+    // table[k][i] = a[i] * ... * a[m - 1] (s <= i < m),
+    // table[k][i] = a[m] * ... * a[i] (m <= i < s + 2^(k + 1)).
+    table: Vec<Vec<S::S>>,
+}
+
+impl<S> DisjointSparseTable<S>
+where
+    S: SemiGroup,
+    S::S: Clone + Copy,
+{
+    pub fn new(a: &[S::S]) -> Self {
+        let len = a.len();
+        let mut table = vec![];
+        let mut k = 0;
+        while 1 << k < len {
+            let half = 1 << k;
+            let mut row = a.to_vec();
+            for mid in (half..len).step_by(2 * half) {
+                for i in (mid - half..mid - 1).rev() {
+                    row[i] = S::op(a[i], row[i + 1]);
+                }
+                for i in mid + 1..(mid + half).min(len) {
+                    row[i] = S::op(row[i - 1], a[i]);
+                }
+            }
+            table.push(row);
+            k += 1;
+        }
+        if table.is_empty() {
+            table.push(a.to_vec());
+        }
+        DisjointSparseTable { len, table }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // return a[l] * ... * a[r - 1], where l < r
+    pub fn fold(&self, l: usize, r: usize) -> S::S {
+        assert!(l < r && r <= self.len);
+        let r = r - 1;
+        if l == r {
+            self.table[0][l]
+        } else {
+            let k = (usize::BITS - 1 - (l ^ r).leading_zeros()) as usize;
+            S::op(self.table[k][l], self.table[k][r])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSparseTable;
+    use crate::algebraic_structures::{
+        algebraical::Algebraical, magma::Magma, monoid::AddMonoid, semigroup::SemiGroup,
+    };
+
+    // composition of affine maps x -> ax + b, which is not commutative
+    struct AffineSemiGroup;
+
+    impl Algebraical for AffineSemiGroup {
+        type S = (i64, i64);
+    }
+
+    impl Magma for AffineSemiGroup {
+        fn op(f: Self::S, g: Self::S) -> Self::S {
+            (f.0 * g.0, f.1 * g.0 + g.1)
+        }
+    }
+
+    impl SemiGroup for AffineSemiGroup {}
+
+    #[test]
+    fn it_works() {
+        let a = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let sum = DisjointSparseTable::<AddMonoid>::new(&a);
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                assert_eq!(sum.fold(l, r), a[l..r].iter().sum::<usize>());
+            }
+        }
+
+        let f = vec![(2, 1), (1, -3), (3, 0), (-1, 2), (1, 1), (2, -2), (5, 4)];
+        let table = DisjointSparseTable::<AffineSemiGroup>::new(&f);
+        for l in 0..f.len() {
+            for r in l + 1..=f.len() {
+                let expected = f[l + 1..r]
+                    .iter()
+                    .fold(f[l], |acc, &g| AffineSemiGroup::op(acc, g));
+                assert_eq!(table.fold(l, r), expected);
+            }
+        }
+
+        let single = DisjointSparseTable::<AddMonoid>::new(&[7]);
+        assert_eq!(single.fold(0, 1), 7);
+    }
+}
@@ -0,0 +1,330 @@
+use crate::{algebraic_structures::monoid::Monoid, data_structures::lazy_segment_tree::Action};
+
+#[derive(Clone, Copy)]
+struct Node<S> {
+    left: Option<usize>,
+    right: Option<usize>,
+    value: S,
+}
+
+// segment tree over [lo, hi) which only allocates the nodes that have been touched
+pub struct DynamicSegmentTree<M>
+where
+    M: Monoid,
+{
+    lo: i64,
+    hi: i64,
+    nodes: Vec<Node<M::S>>,
+}
+
+impl<M> DynamicSegmentTree<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    // every element is M::id() at first
+    // hi - lo must fit in i64
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi && hi.checked_sub(lo).is_some());
+        DynamicSegmentTree {
+            lo,
+            hi,
+            nodes: vec![Node {
+                left: None,
+                right: None,
+                value: M::id(),
+            }],
+        }
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.nodes.push(Node {
+            left: None,
+            right: None,
+            value: M::id(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn value(&self, node: Option<usize>) -> M::S {
+        node.map_or(M::id(), |node| self.nodes[node].value)
+    }
+
+    // O(log(hi - lo))
+    pub fn update(&mut self, idx: i64, x: M::S) {
+        assert!(self.lo <= idx && idx < self.hi);
+        let (mut lo, mut hi) = (self.lo, self.hi);
+        let mut path = vec![0];
+        while hi - lo > 1 {
+            let node = *path.last().unwrap();
+            let mid = lo + (hi - lo) / 2;
+            let child = if idx < mid {
+                hi = mid;
+                self.nodes[node].left
+            } else {
+                lo = mid;
+                self.nodes[node].right
+            };
+            let child = match child {
+                Some(child) => child,
+                None => {
+                    let child = self.new_node();
+                    if idx < mid {
+                        self.nodes[node].left = Some(child);
+                    } else {
+                        self.nodes[node].right = Some(child);
+                    }
+                    child
+                }
+            };
+            path.push(child);
+        }
+
+        let leaf = path.pop().unwrap();
+        self.nodes[leaf].value = x;
+        while let Some(node) = path.pop() {
+            let Node { left, right, .. } = self.nodes[node];
+            self.nodes[node].value = M::op(self.value(left), self.value(right));
+        }
+    }
+
+    pub fn get(&self, idx: i64) -> M::S {
+        assert!(self.lo <= idx && idx < self.hi);
+        self.fold(idx, idx + 1)
+    }
+
+    // return a[l] * ... * a[r - 1]
+    pub fn fold(&self, l: i64, r: i64) -> M::S {
+        self.fold_rec(Some(0), self.lo, self.hi, l, r)
+    }
+
+    fn fold_rec(&self, node: Option<usize>, lo: i64, hi: i64, l: i64, r: i64) -> M::S {
+        match node {
+            Some(node) if lo < r && l < hi => {
+                if l <= lo && hi <= r {
+                    self.nodes[node].value
+                } else {
+                    let mid = lo + (hi - lo) / 2;
+                    M::op(
+                        self.fold_rec(self.nodes[node].left, lo, mid, l, r),
+                        self.fold_rec(self.nodes[node].right, mid, hi, l, r),
+                    )
+                }
+            }
+            _ => M::id(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct LazyNode<S, T> {
+    left: Option<usize>,
+    right: Option<usize>,
+    value: S,
+    lazy: T,
+}
+
+// lazy version of DynamicSegmentTree, the untouched elements are X::id()
+pub struct DynamicLazySegmentTree<X, M>
+where
+    X: Monoid,
+    M: Monoid,
+{
+    lo: i64,
+    hi: i64,
+    nodes: Vec<LazyNode<X::S, M::S>>,
+    action: Action<X, M>,
+}
+
+impl<X, M> DynamicLazySegmentTree<X, M>
+where
+    X: Monoid,
+    X::S: Clone + Copy,
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    // hi - lo must fit in i64
+    pub fn new(lo: i64, hi: i64, f: Action<X, M>) -> Self {
+        assert!(lo < hi && hi.checked_sub(lo).is_some());
+        let mut res = DynamicLazySegmentTree {
+            lo,
+            hi,
+            nodes: vec![],
+            action: f,
+        };
+        res.new_node();
+        res
+    }
+
+    fn new_node(&mut self) -> usize {
+        self.nodes.push(LazyNode {
+            left: None,
+            right: None,
+            value: X::id(),
+            lazy: M::id(),
+        });
+        self.nodes.len() - 1
+    }
+
+    fn apply(&mut self, node: usize, f: M::S) {
+        self.nodes[node].value = (self.action)(self.nodes[node].value, f);
+        self.nodes[node].lazy = M::op(self.nodes[node].lazy, f);
+    }
+
+    // create the children and pass the lazy value to them
+    fn propagate(&mut self, node: usize) -> (usize, usize) {
+        let left = match self.nodes[node].left {
+            Some(left) => left,
+            None => {
+                let left = self.new_node();
+                self.nodes[node].left = Some(left);
+                left
+            }
+        };
+        let right = match self.nodes[node].right {
+            Some(right) => right,
+            None => {
+                let right = self.new_node();
+                self.nodes[node].right = Some(right);
+                right
+            }
+        };
+        let lazy = self.nodes[node].lazy;
+        self.apply(left, lazy);
+        self.apply(right, lazy);
+        self.nodes[node].lazy = M::id();
+        (left, right)
+    }
+
+    // a[i] = f(a[i], x) for l <= i < r
+    pub fn update(&mut self, l: i64, r: i64, x: M::S) {
+        self.update_rec(0, self.lo, self.hi, l, r, x);
+    }
+
+    fn update_rec(&mut self, node: usize, lo: i64, hi: i64, l: i64, r: i64, x: M::S) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self.apply(node, x);
+            return;
+        }
+        let (left, right) = self.propagate(node);
+        let mid = lo + (hi - lo) / 2;
+        self.update_rec(left, lo, mid, l, r, x);
+        self.update_rec(right, mid, hi, l, r, x);
+        self.nodes[node].value = X::op(self.nodes[left].value, self.nodes[right].value);
+    }
+
+    // return a[l] * ... * a[r - 1], without creating nodes
+    pub fn fold(&self, l: i64, r: i64) -> X::S {
+        self.fold_rec(Some(0), self.lo, self.hi, l, r, M::id())
+    }
+
+    // lazy is the composition of the lazy values of the ancestors, which are not passed to node yet
+    // A missing node is X::id() without lazy value, which is what propagate would create.
+    fn fold_rec(&self, node: Option<usize>, lo: i64, hi: i64, l: i64, r: i64, lazy: M::S) -> X::S {
+        if r <= lo || hi <= l {
+            return X::id();
+        }
+        let (value, left, right, node_lazy) = match node {
+            Some(node) => {
+                let LazyNode {
+                    left,
+                    right,
+                    value,
+                    lazy,
+                } = self.nodes[node];
+                (value, left, right, lazy)
+            }
+            None => (X::id(), None, None, M::id()),
+        };
+        if l <= lo && hi <= r {
+            return (self.action)(value, lazy);
+        }
+        let lazy = M::op(node_lazy, lazy);
+        let mid = lo + (hi - lo) / 2;
+        X::op(
+            self.fold_rec(left, lo, mid, l, r, lazy),
+            self.fold_rec(right, mid, hi, l, r, lazy),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicLazySegmentTree, DynamicSegmentTree};
+    use crate::algebraic_structures::monoid::{AddMonoid, MaxMonoid, MinMonoid, RightMonoid};
+
+    const LIMIT: i64 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn dynamic_segment_tree_test() {
+        let mut seg = DynamicSegmentTree::<AddMonoid>::new(-LIMIT, LIMIT);
+        seg.update(-LIMIT, 3);
+        seg.update(0, 5);
+        seg.update(LIMIT - 1, 7);
+        seg.update(123_456_789_012, 11);
+        assert_eq!(seg.fold(-LIMIT, LIMIT), 26);
+        assert_eq!(seg.fold(-LIMIT, 0), 3);
+        assert_eq!(seg.fold(0, 123_456_789_012), 5);
+        assert_eq!(seg.fold(0, 123_456_789_013), 16);
+        assert_eq!(seg.fold(1, LIMIT - 1), 11);
+        assert_eq!(seg.get(0), 5);
+        assert_eq!(seg.get(1), 0);
+        seg.update(0, 1);
+        assert_eq!(seg.fold(-5, 5), 1);
+        assert!(seg.nodes.len() <= 4 * 64);
+    }
+
+    #[test]
+    fn dynamic_lazy_segment_tree_test() {
+        let mut seg =
+            DynamicLazySegmentTree::<MaxMonoid, AddMonoid>::new(0, LIMIT, Box::new(|x, m| x + m));
+        seg.update(10, 1_000_000_000, 3);
+        seg.update(500, LIMIT, 2);
+        seg.update(999_999_999, 1_000_000_001, 10);
+        assert_eq!(seg.fold(0, 10), 0);
+        assert_eq!(seg.fold(0, 11), 3);
+        assert_eq!(seg.fold(0, 501), 5);
+        assert_eq!(seg.fold(0, LIMIT), 15);
+        assert_eq!(seg.fold(1_000_000_000, LIMIT), 12);
+        assert_eq!(seg.fold(1_000_000_001, LIMIT), 2);
+
+        let mut seg = DynamicLazySegmentTree::<MinMonoid, RightMonoid>::new(
+            -LIMIT,
+            LIMIT,
+            Box::new(|x, m| if m == usize::MAX { x } else { m }),
+        );
+        seg.update(-100, 100, 5);
+        seg.update(0, 50, 8);
+        seg.update(40, 45, 2);
+        assert_eq!(seg.fold(-LIMIT, -100), usize::MAX);
+        assert_eq!(seg.fold(-LIMIT, LIMIT), 2);
+        assert_eq!(seg.fold(0, 40), 8);
+        assert_eq!(seg.fold(0, 41), 2);
+        assert_eq!(seg.fold(-1, 1), 5);
+        assert_eq!(seg.fold(45, 50), 8);
+    }
+
+    #[test]
+    fn fold_does_not_allocate() {
+        let mut seg =
+            DynamicLazySegmentTree::<MaxMonoid, AddMonoid>::new(0, LIMIT, Box::new(|x, m| x + m));
+        seg.update(0, LIMIT, 4);
+        seg.update(100, 200, 3);
+        seg.update(150, 160, 1);
+        let len = seg.nodes.len();
+        assert_eq!(seg.fold(0, 100), 4);
+        assert_eq!(seg.fold(123, 124), 7);
+        assert_eq!(seg.fold(155, 1_000_000), 8);
+        assert_eq!(seg.fold(LIMIT - 1, LIMIT), 4);
+        assert_eq!(seg.nodes.len(), len);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_wide_range() {
+        DynamicSegmentTree::<AddMonoid>::new(i64::MIN, i64::MAX);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{algebraic_structures::group::AddGroup, data_structures::fenwick_tree::FenwickTree};
+
+// multiset of integers in [0, size)
+pub struct FenwickMultiSet {
+    len: usize,
+    count: FenwickTree<AddGroup>,
+}
+
+impl FenwickMultiSet {
+    pub fn new(size: usize) -> FenwickMultiSet {
+        FenwickMultiSet {
+            len: 0,
+            count: FenwickTree::new(size),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, x: usize) {
+        self.count.add(x, 1);
+        self.len += 1;
+    }
+
+    // remove one x, return false if x is not contained (including x >= size)
+    pub fn remove(&mut self, x: usize) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        self.count.add(x, -1);
+        self.len -= 1;
+        true
+    }
+
+    // 0 for x >= size, as rank clamps x to size
+    pub fn count(&self, x: usize) -> usize {
+        if x < self.count.len() {
+            self.count.get(x) as usize
+        } else {
+            0
+        }
+    }
+
+    // the number of elements less than x
+    pub fn rank(&self, x: usize) -> usize {
+        self.count.prefix_sum(x.min(self.count.len())) as usize
+    }
+
+    // the k-th smallest element (0-based)
+    pub fn kth(&self, k: usize) -> Option<usize> {
+        if k < self.len {
+            Some(self.count.lower_bound(k as i64 + 1))
+        } else {
+            None
+        }
+    }
+
+    pub fn min(&self) -> Option<usize> {
+        self.kth(0)
+    }
+
+    pub fn max(&self) -> Option<usize> {
+        self.len.checked_sub(1).and_then(|k| self.kth(k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FenwickMultiSet;
+
+    #[test]
+    fn it_works() {
+        let mut set = FenwickMultiSet::new(10);
+        assert_eq!(set.min(), None);
+        set.insert(3);
+        set.insert(7);
+        set.insert(3);
+        set.insert(0);
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.count(3), 2);
+        assert_eq!(set.rank(3), 1);
+        assert_eq!(set.rank(4), 3);
+        assert_eq!(set.rank(100), 4);
+        assert_eq!(set.kth(0), Some(0));
+        assert_eq!(set.kth(1), Some(3));
+        assert_eq!(set.kth(2), Some(3));
+        assert_eq!(set.kth(3), Some(7));
+        assert_eq!(set.kth(4), None);
+
+        assert!(set.remove(3));
+        assert!(!set.remove(5));
+        assert_eq!(set.count(10), 0);
+        assert!(!set.remove(100));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.kth(2), Some(7));
+        assert_eq!(set.min(), Some(0));
+        assert_eq!(set.max(), Some(7));
+    }
+}
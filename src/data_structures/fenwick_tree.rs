@@ -1,60 +1,345 @@
-pub struct FenwickTree<T> {
+use crate::algebraic_structures::{commutativity::Commutativity, group::Group};
+use std::ops::{Bound, RangeBounds};
+
+fn to_half_open<R>(range: R, size: usize) -> (usize, usize)
+where
+    R: RangeBounds<usize>,
+{
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => size,
+    };
+    assert!(l <= r && r <= size);
+    (l, r)
+}
+
+// a * n = a + a + ... + a (n times)
+fn multiply<G>(mut a: G::S, mut n: usize) -> G::S
+where
+    G: Group,
+    G::S: Clone + Copy,
+{
+    let mut res = G::id();
+    while n > 0 {
+        if n % 2 == 1 {
+            res = G::op(res, a);
+        }
+        a = G::op(a, a);
+        n /= 2;
+    }
+    res
+}
+
+// 0-based numbering
+pub struct FenwickTree<G>
+where
+    G: Group + Commutativity,
+{
     size: usize,
-    data: Vec<T>,
-    element: T,
+    // data[i - 1] = a[i - lsb(i)] + ... + a[i - 1]
+    data: Vec<G::S>,
 }
 
-// [CAUTION] 1-based numbering
-impl<T> FenwickTree<T>
+impl<G> FenwickTree<G>
 where
-    T: Clone + std::ops::Add<Output = T> + std::ops::AddAssign,
+    G: Group + Commutativity,
+    G::S: Clone + Copy,
 {
-    pub fn new(size: usize, element: T) -> FenwickTree<T> {
+    pub fn new(size: usize) -> FenwickTree<G> {
         FenwickTree {
             size,
-            data: vec![element.clone(); size + 1],
-            element,
+            data: vec![G::id(); size],
         }
     }
 
-    pub fn add(&mut self, mut idx: usize, a: T) {
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // a[idx] += x
+    pub fn add(&mut self, idx: usize, x: G::S) {
+        let mut idx = idx + 1;
         while idx <= self.size {
-            self.data[idx] = self.data[idx].clone() + a.clone();
-            idx += (!idx + 1) & idx;
+            self.data[idx - 1] = G::op(self.data[idx - 1], x);
+            idx += idx & idx.wrapping_neg();
         }
     }
 
-    // return data[1] + ... + data[idx];
-    pub fn sum(&self, mut idx: usize) -> T {
-        let mut res = self.element.clone();
-        while idx > 0 {
-            res += self.data[idx].clone();
-            idx -= (!idx + 1) & idx;
+    // return a[0] + ... + a[r - 1]
+    pub fn prefix_sum(&self, mut r: usize) -> G::S {
+        let mut res = G::id();
+        while r > 0 {
+            res = G::op(res, self.data[r - 1]);
+            r -= r & r.wrapping_neg();
         }
         res
     }
 
-    pub fn lower_bound(&mut self, key: T) -> usize
+    pub fn sum<R>(&self, range: R) -> G::S
     where
-        T: Ord,
+        R: RangeBounds<usize>,
     {
-        let mut sum = self.element.clone();
-        let mut ng = 0;
-        let mut len = 1;
+        let (l, r) = to_half_open(range, self.size);
+        G::op(self.prefix_sum(r), G::inverse(self.prefix_sum(l)))
+    }
 
-        //Get most significant bit of self.size
-        while self.size & !len >= len {
-            len <<= 1;
-        }
+    pub fn get(&self, idx: usize) -> G::S {
+        self.sum(idx..=idx)
+    }
+
+    pub fn set(&mut self, idx: usize, x: G::S) {
+        let d = G::op(x, G::inverse(self.get(idx)));
+        self.add(idx, d);
+    }
+
+    // return the minimum idx such that a[0] + ... + a[idx] >= key, or len() if there is no such idx
+    // [CAUTION] every a[i] must be non-negative
+    pub fn lower_bound(&self, key: G::S) -> usize
+    where
+        G::S: Ord,
+    {
+        let mut sum = G::id();
+        let mut ng = 0;
+        let mut len = if self.size == 0 {
+            0
+        } else {
+            1 << (usize::BITS - 1 - self.size.leading_zeros())
+        };
 
         while len > 0 {
             let idx = ng + len;
-            if idx <= self.size && sum.clone() + self.data[idx].clone() < key {
-                sum += self.data[idx].clone();
+            if idx <= self.size && G::op(sum, self.data[idx - 1]) < key {
+                sum = G::op(sum, self.data[idx - 1]);
                 ng += len;
             }
             len >>= 1;
         }
-        ng + 1
+        ng
+    }
+}
+
+// O(n)
+impl<G> From<Vec<G::S>> for FenwickTree<G>
+where
+    G: Group + Commutativity,
+    G::S: Clone + Copy,
+{
+    fn from(mut data: Vec<G::S>) -> FenwickTree<G> {
+        let size = data.len();
+        for i in 1..=size {
+            let j = i + (i & i.wrapping_neg());
+            if j <= size {
+                data[j - 1] = G::op(data[j - 1], data[i - 1]);
+            }
+        }
+        FenwickTree { size, data }
+    }
+}
+
+// range add, range sum
+// sum(0..r) = b1.prefix_sum(r) * r + b0.prefix_sum(r)
+pub struct RangeAddFenwickTree<G>
+where
+    G: Group + Commutativity,
+{
+    b0: FenwickTree<G>,
+    b1: FenwickTree<G>,
+}
+
+impl<G> RangeAddFenwickTree<G>
+where
+    G: Group + Commutativity,
+    G::S: Clone + Copy,
+{
+    pub fn new(size: usize) -> RangeAddFenwickTree<G> {
+        RangeAddFenwickTree {
+            b0: FenwickTree::new(size + 1),
+            b1: FenwickTree::new(size + 1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.b0.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // a[i] += x for i in range
+    pub fn add<R>(&mut self, range: R, x: G::S)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (l, r) = to_half_open(range, self.len());
+        self.b0.add(l, G::inverse(multiply::<G>(x, l)));
+        self.b0.add(r, multiply::<G>(x, r));
+        self.b1.add(l, x);
+        self.b1.add(r, G::inverse(x));
+    }
+
+    // return a[0] + ... + a[r - 1]
+    pub fn prefix_sum(&self, r: usize) -> G::S {
+        G::op(
+            multiply::<G>(self.b1.prefix_sum(r), r),
+            self.b0.prefix_sum(r),
+        )
+    }
+
+    pub fn sum<R>(&self, range: R) -> G::S
+    where
+        R: RangeBounds<usize>,
+    {
+        let (l, r) = to_half_open(range, self.len());
+        G::op(self.prefix_sum(r), G::inverse(self.prefix_sum(l)))
+    }
+}
+
+// point add, rectangle sum
+pub struct FenwickTree2D<G>
+where
+    G: Group + Commutativity,
+{
+    height: usize,
+    width: usize,
+    data: Vec<Vec<G::S>>,
+}
+
+impl<G> FenwickTree2D<G>
+where
+    G: Group + Commutativity,
+    G::S: Clone + Copy,
+{
+    pub fn new(height: usize, width: usize) -> FenwickTree2D<G> {
+        FenwickTree2D {
+            height,
+            width,
+            data: vec![vec![G::id(); width]; height],
+        }
+    }
+
+    // a[i][j] += x
+    pub fn add(&mut self, i: usize, j: usize, x: G::S) {
+        let mut i = i + 1;
+        while i <= self.height {
+            let mut j = j + 1;
+            while j <= self.width {
+                self.data[i - 1][j - 1] = G::op(self.data[i - 1][j - 1], x);
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // return the sum of a[i][j] for i < r, j < c
+    pub fn prefix_sum(&self, mut r: usize, c: usize) -> G::S {
+        let mut res = G::id();
+        while r > 0 {
+            let mut c = c;
+            while c > 0 {
+                res = G::op(res, self.data[r - 1][c - 1]);
+                c -= c & c.wrapping_neg();
+            }
+            r -= r & r.wrapping_neg();
+        }
+        res
+    }
+
+    pub fn sum<R, C>(&self, rows: R, columns: C) -> G::S
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (r0, r1) = to_half_open(rows, self.height);
+        let (c0, c1) = to_half_open(columns, self.width);
+        G::op(
+            G::op(self.prefix_sum(r1, c1), self.prefix_sum(r0, c0)),
+            G::inverse(G::op(self.prefix_sum(r0, c1), self.prefix_sum(r1, c0))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FenwickTree, FenwickTree2D, RangeAddFenwickTree};
+    use crate::algebraic_structures::group::{AddGroup, XorGroup};
+
+    #[test]
+    fn fenwick_tree_test() {
+        let mut ft = FenwickTree::<AddGroup>::new(5);
+        ft.add(0, 3);
+        ft.add(2, 1);
+        ft.add(4, 4);
+        ft.add(2, 2);
+        assert_eq!(ft.prefix_sum(0), 0);
+        assert_eq!(ft.prefix_sum(3), 6);
+        assert_eq!(ft.sum(..), 10);
+        assert_eq!(ft.sum(1..3), 3);
+        assert_eq!(ft.sum(2..=4), 7);
+        assert_eq!(ft.get(2), 3);
+        ft.set(2, -1);
+        assert_eq!(ft.sum(2..), 3);
+
+        let ft = FenwickTree::<AddGroup>::from(vec![3, 1, 4, 1, 5, 9, 2]);
+        for l in 0..=7 {
+            for r in l..=7 {
+                assert_eq!(
+                    ft.sum(l..r),
+                    [3, 1, 4, 1, 5, 9, 2][l..r].iter().sum::<i64>()
+                );
+            }
+        }
+        assert_eq!(ft.lower_bound(0), 0);
+        assert_eq!(ft.lower_bound(4), 1);
+        assert_eq!(ft.lower_bound(5), 2);
+        assert_eq!(ft.lower_bound(9), 3);
+        assert_eq!(ft.lower_bound(25), 6);
+        assert_eq!(ft.lower_bound(26), 7);
+
+        let ft = FenwickTree::<XorGroup>::from(vec![0b001, 0b011, 0b110, 0b100]);
+        assert_eq!(ft.sum(1..3), 0b101);
+        assert_eq!(ft.sum(..), 0b000);
+    }
+
+    #[test]
+    fn range_add_test() {
+        let mut ft = RangeAddFenwickTree::<AddGroup>::new(6);
+        let mut naive = [0; 6];
+        for &(l, r, x) in &[(0, 3, 2), (2, 6, -1), (1, 2, 5), (4, 5, 7)] {
+            ft.add(l..r, x);
+            for a in &mut naive[l..r] {
+                *a += x;
+            }
+        }
+        for l in 0..=6 {
+            for r in l..=6 {
+                assert_eq!(ft.sum(l..r), naive[l..r].iter().sum::<i64>());
+            }
+        }
+    }
+
+    #[test]
+    fn fenwick_tree_2d_test() {
+        let mut ft = FenwickTree2D::<AddGroup>::new(3, 4);
+        ft.add(0, 0, 1);
+        ft.add(1, 2, 5);
+        ft.add(2, 3, -2);
+        ft.add(2, 0, 3);
+        assert_eq!(ft.sum(.., ..), 7);
+        assert_eq!(ft.sum(1..3, 2..4), 3);
+        assert_eq!(ft.sum(0..2, 0..3), 6);
+        assert_eq!(ft.sum(2..3, ..), 1);
+        assert_eq!(ft.sum(1..=1, 2..=2), 5);
+        assert_eq!(ft.sum(1..1, ..), 0);
     }
 }
@@ -0,0 +1,415 @@
+use crate::{
+    algebraic_structures::{algebraical::Algebraical, monoid::Monoid},
+    data_structures::lazy_segment_tree::Action,
+};
+use std::{mem, rc::Rc};
+
+type Link<S, T> = Option<Box<Node<S, T>>>;
+type Pair<S, T> = (Link<S, T>, Link<S, T>);
+type Triple<S, T> = (Link<S, T>, Link<S, T>, Link<S, T>);
+// shared by the treaps created by split_off
+type SharedAction<X, M> =
+    Rc<dyn Fn(<X as Algebraical>::S, <M as Algebraical>::S) -> <X as Algebraical>::S>;
+
+struct Node<S, T> {
+    value: S,
+    // fold of the subtree from left to right, and from right to left
+    fold: S,
+    rev_fold: S,
+    lazy: T,
+    rev: bool,
+    size: usize,
+    priority: u64,
+    left: Link<S, T>,
+    right: Link<S, T>,
+}
+
+// Treap keyed by the position in the sequence
+// insert, erase, split, merge, fold, reverse and apply in expected O(log n)
+pub struct ImplicitTreap<X, M>
+where
+    X: Monoid,
+    M: Monoid,
+{
+    root: Link<X::S, M::S>,
+    action: SharedAction<X, M>,
+    seed: u64,
+}
+
+impl<X, M> ImplicitTreap<X, M>
+where
+    X: Monoid,
+    X::S: Clone + Copy,
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    pub fn new(f: Action<X, M>) -> Self {
+        ImplicitTreap {
+            root: None,
+            action: Rc::from(f),
+            seed: 88172645463325252,
+        }
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    fn size(node: &Link<X::S, M::S>) -> usize {
+        node.as_ref().map_or(0, |node| node.size)
+    }
+
+    fn fold_of(node: &Link<X::S, M::S>) -> X::S {
+        node.as_ref().map_or(X::id(), |node| node.fold)
+    }
+
+    fn rev_fold_of(node: &Link<X::S, M::S>) -> X::S {
+        node.as_ref().map_or(X::id(), |node| node.rev_fold)
+    }
+
+    fn update(node: &mut Node<X::S, M::S>) {
+        node.size = Self::size(&node.left) + 1 + Self::size(&node.right);
+        node.fold = X::op(
+            X::op(Self::fold_of(&node.left), node.value),
+            Self::fold_of(&node.right),
+        );
+        node.rev_fold = X::op(
+            X::op(Self::rev_fold_of(&node.right), node.value),
+            Self::rev_fold_of(&node.left),
+        );
+    }
+
+    fn apply(&self, node: &mut Link<X::S, M::S>, x: M::S) {
+        if let Some(node) = node {
+            node.value = (self.action)(node.value, x);
+            node.fold = (self.action)(node.fold, x);
+            node.rev_fold = (self.action)(node.rev_fold, x);
+            node.lazy = M::op(node.lazy, x);
+        }
+    }
+
+    fn toggle(node: &mut Link<X::S, M::S>) {
+        if let Some(node) = node {
+            mem::swap(&mut node.left, &mut node.right);
+            mem::swap(&mut node.fold, &mut node.rev_fold);
+            node.rev ^= true;
+        }
+    }
+
+    fn propagate(&self, node: &mut Node<X::S, M::S>) {
+        let lazy = mem::replace(&mut node.lazy, M::id());
+        self.apply(&mut node.left, lazy);
+        self.apply(&mut node.right, lazy);
+        if node.rev {
+            Self::toggle(&mut node.left);
+            Self::toggle(&mut node.right);
+            node.rev = false;
+        }
+    }
+
+    // split into the first k elements and the rest
+    fn split(&self, node: Link<X::S, M::S>, k: usize) -> Pair<X::S, M::S> {
+        match node {
+            None => (None, None),
+            Some(mut node) => {
+                self.propagate(&mut node);
+                let left_size = Self::size(&node.left);
+                if k <= left_size {
+                    let (l, r) = self.split(node.left.take(), k);
+                    node.left = r;
+                    Self::update(&mut node);
+                    (l, Some(node))
+                } else {
+                    let (l, r) = self.split(node.right.take(), k - left_size - 1);
+                    node.right = l;
+                    Self::update(&mut node);
+                    (Some(node), r)
+                }
+            }
+        }
+    }
+
+    fn merge(&self, l: Link<X::S, M::S>, r: Link<X::S, M::S>) -> Link<X::S, M::S> {
+        match (l, r) {
+            (None, r) => r,
+            (l, None) => l,
+            (Some(mut l), Some(mut r)) => {
+                if l.priority > r.priority {
+                    self.propagate(&mut l);
+                    l.right = self.merge(l.right.take(), Some(r));
+                    Self::update(&mut l);
+                    Some(l)
+                } else {
+                    self.propagate(&mut r);
+                    r.left = self.merge(Some(l), r.left.take());
+                    Self::update(&mut r);
+                    Some(r)
+                }
+            }
+        }
+    }
+
+    // split into [0, l), [l, r), [r, len)
+    fn split3(&mut self, l: usize, r: usize) -> Triple<X::S, M::S> {
+        assert!(l <= r && r <= self.len());
+        let root = self.root.take();
+        let (a, bc) = self.split(root, l);
+        let (b, c) = self.split(bc, r - l);
+        (a, b, c)
+    }
+
+    fn merge3(&mut self, a: Link<X::S, M::S>, b: Link<X::S, M::S>, c: Link<X::S, M::S>) {
+        let ab = self.merge(a, b);
+        self.root = self.merge(ab, c);
+    }
+
+    pub fn len(&self) -> usize {
+        Self::size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    // insert x so that it becomes the idx-th element
+    pub fn insert(&mut self, idx: usize, x: X::S) {
+        assert!(idx <= self.len());
+        let node = Some(Box::new(Node {
+            value: x,
+            fold: x,
+            rev_fold: x,
+            lazy: M::id(),
+            rev: false,
+            size: 1,
+            priority: self.next_priority(),
+            left: None,
+            right: None,
+        }));
+        let root = self.root.take();
+        let (a, c) = self.split(root, idx);
+        self.merge3(a, node, c);
+    }
+
+    pub fn push_back(&mut self, x: X::S) {
+        self.insert(self.len(), x);
+    }
+
+    // remove the idx-th element and return it
+    pub fn erase(&mut self, idx: usize) -> X::S {
+        let (a, b, c) = self.split3(idx, idx + 1);
+        let res = b.as_ref().unwrap().value;
+        self.root = self.merge(a, c);
+        res
+    }
+
+    // the idx-th element (0-based)
+    pub fn kth(&mut self, idx: usize) -> X::S {
+        self.fold(idx, idx + 1)
+    }
+
+    // return a[l] * ... * a[r - 1]
+    pub fn fold(&mut self, l: usize, r: usize) -> X::S {
+        let (a, b, c) = self.split3(l, r);
+        let res = Self::fold_of(&b);
+        self.merge3(a, b, c);
+        res
+    }
+
+    // reverse a[l..r]
+    pub fn reverse(&mut self, l: usize, r: usize) {
+        let (a, mut b, c) = self.split3(l, r);
+        Self::toggle(&mut b);
+        self.merge3(a, b, c);
+    }
+
+    // a[i] = f(a[i], x) for l <= i < r
+    pub fn apply_range(&mut self, l: usize, r: usize, x: M::S) {
+        let (a, mut b, c) = self.split3(l, r);
+        self.apply(&mut b, x);
+        self.merge3(a, b, c);
+    }
+
+    // split off a[at..] as another treap, leaving a[..at]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len());
+        let root = self.root.take();
+        let (a, b) = self.split(root, at);
+        self.root = a;
+        let seed = self.next_priority();
+        ImplicitTreap {
+            root: b,
+            action: Rc::clone(&self.action),
+            seed,
+        }
+    }
+
+    // concatenate other to the end of self
+    pub fn append(&mut self, other: Self) {
+        let root = self.root.take();
+        self.root = self.merge(root, other.root);
+    }
+
+    pub fn to_vec(&mut self) -> Vec<X::S> {
+        let mut res = Vec::with_capacity(self.len());
+        let mut root = self.root.take();
+        self.collect(&mut root, &mut res);
+        self.root = root;
+        res
+    }
+
+    fn collect(&self, node: &mut Link<X::S, M::S>, res: &mut Vec<X::S>) {
+        if let Some(node) = node {
+            self.propagate(node);
+            self.collect(&mut node.left, res);
+            res.push(node.value);
+            self.collect(&mut node.right, res);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImplicitTreap;
+    use crate::algebraic_structures::{
+        algebraical::Algebraical, identity::Identity, magma::Magma, monoid::Monoid,
+        semigroup::SemiGroup,
+    };
+
+    // concatenation of strings of digits, represented as (value, 10^len), which is not commutative
+    struct ConcatMonoid;
+
+    impl Algebraical for ConcatMonoid {
+        type S = (u64, u64);
+    }
+
+    impl Magma for ConcatMonoid {
+        fn op(a: Self::S, b: Self::S) -> Self::S {
+            (a.0 * b.1 + b.0, a.1 * b.1)
+        }
+    }
+
+    impl Identity for ConcatMonoid {
+        fn id() -> Self::S {
+            (0, 1)
+        }
+    }
+
+    impl SemiGroup for ConcatMonoid {}
+
+    impl Monoid for ConcatMonoid {}
+
+    // (sum, len)
+    struct SumMonoid;
+
+    impl Algebraical for SumMonoid {
+        type S = (i64, i64);
+    }
+
+    impl Magma for SumMonoid {
+        fn op(a: Self::S, b: Self::S) -> Self::S {
+            (a.0 + b.0, a.1 + b.1)
+        }
+    }
+
+    impl Identity for SumMonoid {
+        fn id() -> Self::S {
+            (0, 0)
+        }
+    }
+
+    impl SemiGroup for SumMonoid {}
+
+    impl Monoid for SumMonoid {}
+
+    struct AddMonoid;
+
+    impl Algebraical for AddMonoid {
+        type S = i64;
+    }
+
+    impl Magma for AddMonoid {
+        fn op(a: Self::S, b: Self::S) -> Self::S {
+            a + b
+        }
+    }
+
+    impl Identity for AddMonoid {
+        fn id() -> Self::S {
+            0
+        }
+    }
+
+    impl SemiGroup for AddMonoid {}
+
+    impl Monoid for AddMonoid {}
+
+    fn digits(treap: &mut ImplicitTreap<ConcatMonoid, AddMonoid>) -> Vec<u64> {
+        treap.to_vec().into_iter().map(|(d, _)| d).collect()
+    }
+
+    #[test]
+    fn it_works() {
+        let mut treap = ImplicitTreap::<ConcatMonoid, AddMonoid>::new(Box::new(|x, _| x));
+        for d in [1, 2, 3, 4, 5] {
+            treap.push_back((d, 10));
+        }
+        assert_eq!(treap.len(), 5);
+        assert_eq!(treap.fold(0, 5).0, 12345);
+        assert_eq!(treap.fold(1, 4).0, 234);
+
+        treap.reverse(1, 4);
+        assert_eq!(digits(&mut treap), vec![1, 4, 3, 2, 5]);
+        assert_eq!(treap.fold(0, 5).0, 14325);
+        assert_eq!(treap.fold(2, 5).0, 325);
+
+        treap.insert(2, (9, 10));
+        assert_eq!(treap.fold(0, 6).0, 149325);
+        assert_eq!(treap.erase(0), (1, 10));
+        assert_eq!(treap.kth(0), (4, 10));
+        treap.reverse(0, 5);
+        assert_eq!(treap.fold(0, 5).0, 52394);
+
+        let mut right = treap.split_off(2);
+        assert_eq!(digits(&mut treap), vec![5, 2]);
+        assert_eq!(digits(&mut right), vec![3, 9, 4]);
+        right.reverse(0, 3);
+        right.append(treap);
+        assert_eq!(right.fold(0, 5).0, 49352);
+        assert_eq!(right.fold(1, 4).0, 935);
+    }
+
+    #[test]
+    fn apply_test() {
+        let mut treap = ImplicitTreap::<SumMonoid, AddMonoid>::new(Box::new(|(sum, len), k| {
+            (sum + k * len, len)
+        }));
+        let mut naive = vec![3, 1, 4, 1, 5, 9, 2, 6];
+        for &x in &naive {
+            treap.push_back((x, 1));
+        }
+        treap.apply_range(1, 5, 10);
+        naive[1..5].iter_mut().for_each(|x| *x += 10);
+        treap.reverse(3, 7);
+        naive[3..7].reverse();
+        treap.apply_range(0, 4, -2);
+        naive[0..4].iter_mut().for_each(|x| *x -= 2);
+        treap.insert(4, (100, 1));
+        naive.insert(4, 100);
+        treap.reverse(0, 9);
+        naive.reverse();
+        assert_eq!(treap.erase(3), (naive.remove(3), 1));
+
+        for l in 0..naive.len() {
+            for r in l..=naive.len() {
+                assert_eq!(treap.fold(l, r), (naive[l..r].iter().sum(), (r - l) as i64));
+            }
+        }
+        assert_eq!(
+            treap.to_vec(),
+            naive.iter().map(|&x| (x, 1)).collect::<Vec<_>>()
+        );
+    }
+}
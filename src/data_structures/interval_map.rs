@@ -0,0 +1,277 @@
+use std::{collections::BTreeMap, ops::Range};
+
+// Disjoint half-open intervals with values, adjacent intervals with the same value are merged
+// assign and erase in amortized O(log n) plus the number of removed pieces
+pub struct IntervalMap<K, V> {
+    // l -> (r, value) for [l, r)
+    map: BTreeMap<K, (K, V)>,
+}
+
+impl<K, V> IntervalMap<K, V>
+where
+    K: Ord + Copy,
+    V: Clone + PartialEq,
+{
+    pub fn new() -> Self {
+        IntervalMap {
+            map: BTreeMap::new(),
+        }
+    }
+
+    // the number of intervals
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // the interval containing x and its value
+    pub fn get_range(&self, x: K) -> Option<(Range<K>, &V)> {
+        let (&l, (r, v)) = self.map.range(..=x).next_back()?;
+        if x < *r {
+            Some((l..*r, v))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: K) -> Option<&V> {
+        self.get_range(x).map(|(_, v)| v)
+    }
+
+    // split the interval containing x into the ones ending at x and starting at x
+    fn split(&mut self, x: K) {
+        if let Some((&l, &(r, _))) = self.map.range(..x).next_back() {
+            if x < r {
+                let entry = self.map.get_mut(&l).unwrap();
+                entry.0 = x;
+                let v = entry.1.clone();
+                self.map.insert(x, (r, v));
+            }
+        }
+    }
+
+    // remove [l, r) and return the removed pieces in increasing order
+    pub fn erase(&mut self, l: K, r: K) -> Vec<(Range<K>, V)> {
+        if l >= r {
+            return vec![];
+        }
+        self.split(l);
+        self.split(r);
+        let keys = self.map.range(l..r).map(|(&k, _)| k).collect::<Vec<_>>();
+        keys.into_iter()
+            .map(|k| {
+                let (e, v) = self.map.remove(&k).unwrap();
+                (k..e, v)
+            })
+            .collect()
+    }
+
+    // set the value of [l, r) to v and return the overwritten pieces in increasing order
+    pub fn assign(&mut self, l: K, r: K, v: V) -> Vec<(Range<K>, V)> {
+        if l >= r {
+            return vec![];
+        }
+        let removed = self.erase(l, r);
+        let (mut l, mut r) = (l, r);
+        if let Some((&a, (b, w))) = self.map.range(..l).next_back() {
+            if *b == l && *w == v {
+                l = a;
+                self.map.remove(&a);
+            }
+        }
+        if let Some((b, w)) = self.map.get(&r) {
+            if *w == v {
+                let b = *b;
+                self.map.remove(&r);
+                r = b;
+            }
+        }
+        self.map.insert(l, (r, v));
+        removed
+    }
+
+    // iterate over the intervals in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = (Range<K>, &V)> {
+        self.map.iter().map(|(&l, (r, v))| (l..*r, v))
+    }
+}
+
+impl<K, V> Default for IntervalMap<K, V>
+where
+    K: Ord + Copy,
+    V: Clone + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Set of points stored as disjoint half-open intervals, overlapping or adjacent ones are merged
+pub struct IntervalSet<T> {
+    map: IntervalMap<T, ()>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    pub fn new() -> Self {
+        IntervalSet {
+            map: IntervalMap::new(),
+        }
+    }
+
+    // the number of intervals
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // add [l, r)
+    pub fn insert(&mut self, l: T, r: T) {
+        self.map.assign(l, r, ());
+    }
+
+    // remove [l, r)
+    pub fn erase(&mut self, l: T, r: T) {
+        self.map.erase(l, r);
+    }
+
+    pub fn covers(&self, x: T) -> bool {
+        self.map.get(x).is_some()
+    }
+
+    // whether [l, r) is covered by one interval, where l < r
+    pub fn covers_range(&self, l: T, r: T) -> bool {
+        self.covered_by(l).is_some_and(|range| r <= range.end)
+    }
+
+    // the interval containing x
+    pub fn covered_by(&self, x: T) -> Option<Range<T>> {
+        self.map.get_range(x).map(|(range, _)| range)
+    }
+
+    // the minimum y >= x not covered
+    pub fn mex(&self, x: T) -> T {
+        self.covered_by(x).map_or(x, |range| range.end)
+    }
+
+    // iterate over the intervals in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.map.iter().map(|(range, _)| range)
+    }
+}
+
+impl<T> Default for IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IntervalMap, IntervalSet};
+    use crate::xorshift::XorShift;
+
+    #[test]
+    fn interval_set_test() {
+        let mut set = IntervalSet::new();
+        set.insert(1, 3);
+        set.insert(5, 8);
+        set.insert(3, 4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..4, 5..8]);
+        set.insert(2, 6);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..8]);
+        set.insert(10, 12);
+        assert!(set.covers(1));
+        assert!(!set.covers(8));
+        assert!(set.covers_range(2, 8));
+        assert!(!set.covers_range(2, 9));
+        assert_eq!(set.covered_by(7), Some(1..8));
+        assert_eq!(set.covered_by(9), None);
+        assert_eq!(set.mex(0), 0);
+        assert_eq!(set.mex(3), 8);
+        assert_eq!(set.mex(10), 12);
+
+        set.erase(3, 5);
+        set.erase(11, 20);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..3, 5..8, 10..11]);
+        assert_eq!(set.len(), 3);
+        set.erase(0, 100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn interval_map_test() {
+        let mut map = IntervalMap::new();
+        assert_eq!(map.assign(0, 10, 'a'), vec![]);
+        assert_eq!(map.assign(3, 5, 'b'), vec![(3..5, 'a')]);
+        assert_eq!(map.assign(5, 7, 'b'), vec![(5..7, 'a')]);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(0..3, &'a'), (3..7, &'b'), (7..10, &'a')]
+        );
+        assert_eq!(map.get(6), Some(&'b'));
+        assert_eq!(map.get(10), None);
+        assert_eq!(
+            map.assign(2, 8, 'a'),
+            vec![(2..3, 'a'), (3..7, 'b'), (7..8, 'a')]
+        );
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..10, &'a')]);
+        assert_eq!(map.erase(4, 12), vec![(4..10, 'a')]);
+        assert_eq!(map.get_range(1), Some((0..4, &'a')));
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let mut map = IntervalMap::new();
+        let mut naive = [None; 50];
+        for _ in 0..2000 {
+            let l = rng.rand(50) as usize;
+            let r = l + rng.rand(50 - l as u64 + 1) as usize;
+            // the removed pieces expanded to points
+            let mut expected = vec![];
+            for (x, v) in naive.iter().enumerate().take(r).skip(l) {
+                if let Some(v) = v {
+                    expected.push((x, *v));
+                }
+            }
+            let removed = if rng.rand(3) == 0 {
+                for x in naive.iter_mut().take(r).skip(l) {
+                    *x = None;
+                }
+                map.erase(l, r)
+            } else {
+                let v = rng.rand(3);
+                for x in naive.iter_mut().take(r).skip(l) {
+                    *x = Some(v);
+                }
+                map.assign(l, r, v)
+            };
+            let removed = removed
+                .into_iter()
+                .flat_map(|(range, v)| range.map(move |x| (x, v)))
+                .collect::<Vec<_>>();
+            assert_eq!(removed, expected);
+            for (x, &v) in naive.iter().enumerate() {
+                assert_eq!(map.get(x).copied(), v);
+            }
+            // adjacent intervals must have different values
+            let intervals = map.iter().collect::<Vec<_>>();
+            for w in intervals.windows(2) {
+                assert!(w[0].0.end < w[1].0.start || w[0].1 != w[1].1);
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::algebraic_structures::monoid::Monoid;
+use crate::algebraic_structures::{algebraical::Algebraical, monoid::Monoid};
+
+// the action of M on X
+pub type Action<X, M> =
+    Box<dyn Fn(<X as Algebraical>::S, <M as Algebraical>::S) -> <X as Algebraical>::S>;
 
 pub struct LazySegmentTree<X, M>
 where
@@ -8,7 +12,7 @@ where
     size: usize,
     data: Vec<X::S>,
     lazy: Vec<M::S>,
-    action: Box<dyn Fn(X::S, M::S) -> X::S>,
+    action: Action<X, M>,
 }
 
 impl<X, M> LazySegmentTree<X, M>
@@ -18,7 +22,7 @@ where
     M: Monoid,
     M::S: Clone + Copy,
 {
-    pub fn new(size: usize, f: Box<dyn Fn(X::S, M::S) -> X::S>) -> Self {
+    pub fn new(size: usize, f: Action<X, M>) -> Self {
         let size = size.next_power_of_two();
         LazySegmentTree::<X, M> {
             size,
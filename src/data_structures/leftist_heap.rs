@@ -0,0 +1,213 @@
+use std::rc::Rc;
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    // the length of the right spine
+    rank: usize,
+    size: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+fn rank<T>(node: &Link<T>) -> usize {
+    node.as_ref().map_or(0, |node| node.rank)
+}
+
+fn size<T>(node: &Link<T>) -> usize {
+    node.as_ref().map_or(0, |node| node.size)
+}
+
+fn new_node<T>(value: T, a: Link<T>, b: Link<T>) -> Link<T> {
+    // keep the right spine the shorter one
+    let (left, right) = if rank(&a) >= rank(&b) { (a, b) } else { (b, a) };
+    Some(Rc::new(Node {
+        value,
+        rank: rank(&right) + 1,
+        size: size(&left) + size(&right) + 1,
+        left,
+        right,
+    }))
+}
+
+fn meld<T>(a: Link<T>, b: Link<T>) -> Link<T>
+where
+    T: Ord + Clone,
+{
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(a), Some(b)) => {
+            let (a, b) = if a.value <= b.value { (a, b) } else { (b, a) };
+            let right = meld(a.right.clone(), Some(b));
+            new_node(a.value.clone(), a.left.clone(), right)
+        }
+    }
+}
+
+// Persistent meldable min-heap, push, pop and meld in O(log n)
+// Cloning is O(1) and the clones share their nodes, so every version stays available.
+pub struct LeftistHeap<T> {
+    root: Link<T>,
+}
+
+impl<T> Clone for LeftistHeap<T> {
+    fn clone(&self) -> Self {
+        LeftistHeap {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<T> LeftistHeap<T>
+where
+    T: Ord + Clone,
+{
+    pub fn new() -> Self {
+        LeftistHeap { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+
+    pub fn push(&mut self, x: T) {
+        self.root = meld(self.root.take(), new_node(x, None, None));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.root = meld(root.left.clone(), root.right.clone());
+        Some(root.value.clone())
+    }
+
+    // move every element of other into self
+    pub fn meld(&mut self, mut other: Self) {
+        self.root = meld(self.root.take(), other.root.take());
+    }
+}
+
+// The default drop is recursive, which overflows the stack on a long chain of left children,
+// such as the one made by pushing decreasing keys. The nodes shared with other versions are kept.
+impl<T> Drop for LeftistHeap<T> {
+    fn drop(&mut self) {
+        let mut stack = vec![];
+        stack.extend(self.root.take());
+        while let Some(node) = stack.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T> Default for LeftistHeap<T>
+where
+    T: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for LeftistHeap<T>
+where
+    T: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = LeftistHeap::new();
+        for x in iter {
+            res.push(x);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LeftistHeap;
+    use crate::xorshift::XorShift;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    #[test]
+    fn it_works() {
+        let mut a = vec![5, 1, 8, 3].into_iter().collect::<LeftistHeap<i32>>();
+        let b = vec![7, 2, 2].into_iter().collect::<LeftistHeap<i32>>();
+        let old = a.clone();
+        a.meld(b.clone());
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.peek(), Some(&1));
+
+        let mut popped = vec![];
+        while let Some(x) = a.pop() {
+            popped.push(x);
+        }
+        assert_eq!(popped, vec![1, 2, 2, 3, 5, 7, 8]);
+
+        // the old versions are not changed
+        assert_eq!(old.len(), 4);
+        assert_eq!(old.peek(), Some(&1));
+        assert_eq!(b.peek(), Some(&2));
+        let mut old = old;
+        old.pop();
+        assert_eq!(old.peek(), Some(&3));
+        assert_eq!(LeftistHeap::<i32>::new().pop(), None);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let mut heaps = vec![LeftistHeap::new()];
+        let mut naive = vec![BinaryHeap::new()];
+        for _ in 0..3000 {
+            let i = rng.rand(heaps.len() as u64) as usize;
+            match rng.rand(4) {
+                0 | 1 => {
+                    let x = rng.rand(1000);
+                    heaps[i].push(x);
+                    naive[i].push(Reverse(x));
+                }
+                2 => {
+                    assert_eq!(heaps[i].pop(), naive[i].pop().map(|Reverse(x)| x));
+                }
+                _ => {
+                    // keep the current version of i and add the meld of i and j as a new heap
+                    let j = rng.rand(heaps.len() as u64) as usize;
+                    let mut heap = heaps[i].clone();
+                    heap.meld(heaps[j].clone());
+                    let mut b = naive[i].clone();
+                    b.extend(naive[j].iter().cloned());
+                    heaps.push(heap);
+                    naive.push(b);
+                }
+            }
+            assert_eq!(heaps[i].len(), naive[i].len());
+            assert_eq!(heaps[i].peek(), naive[i].peek().map(|Reverse(x)| x));
+        }
+    }
+
+    #[test]
+    fn drop_long_chain() {
+        let mut heap = LeftistHeap::new();
+        for x in (0..1_000_000).rev() {
+            heap.push(x);
+        }
+        let old = heap.clone();
+        heap.pop();
+        assert_eq!(old.len(), 1_000_000);
+        drop(old);
+        assert_eq!(heap.peek(), Some(&1));
+        drop(heap);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    algorithms::binary_search::BinarySearch, data_structures::convex_hull_trick::LineNumber,
+};
+
+// Li Chao tree over the given x coordinates
+// add_line, add_segment and query in O(log n) (add_segment is O(log^2 n))
+// The lines are stored negated when maximizing, so that the tree always keeps the minimum.
+pub struct LiChaoTree<T> {
+    minimize: bool,
+    // sorted distinct x coordinates
+    xs: Vec<T>,
+    size: usize,
+    lines: Vec<Option<(T, T)>>,
+}
+
+impl<T> LiChaoTree<T>
+where
+    T: LineNumber,
+{
+    pub fn new(xs: &[T], minimize: bool) -> Self {
+        let mut xs = xs.to_vec();
+        xs.sort();
+        xs.dedup();
+        assert!(!xs.is_empty());
+        let size = xs.len().next_power_of_two();
+        LiChaoTree {
+            minimize,
+            xs,
+            size,
+            lines: vec![None; 2 * size],
+        }
+    }
+
+    // the indices beyond the coordinates are regarded as the last coordinate
+    fn x(&self, i: usize) -> T {
+        self.xs[i.min(self.xs.len() - 1)]
+    }
+
+    fn eval(line: (T, T), x: T) -> T::Wide {
+        T::eval_line(line.0, line.1, x)
+    }
+
+    // add line to the node covering [l, r)
+    fn add(&mut self, mut node: usize, mut l: usize, mut r: usize, mut line: (T, T)) {
+        loop {
+            let cur = match self.lines[node] {
+                Some(cur) => cur,
+                None => {
+                    self.lines[node] = Some(line);
+                    return;
+                }
+            };
+            let (xl, xr) = (self.x(l), self.x(r - 1));
+            let left_better = Self::eval(line, xl) < Self::eval(cur, xl);
+            let right_better = Self::eval(line, xr) < Self::eval(cur, xr);
+            if left_better && right_better {
+                self.lines[node] = Some(line);
+                return;
+            }
+            if !left_better && !right_better {
+                return;
+            }
+            let mid = (l + r) / 2;
+            let xm = self.x(mid);
+            // keep the better one at mid, and carry the other to the side where it can win
+            let (kept, carried) = if Self::eval(line, xm) < Self::eval(cur, xm) {
+                (line, cur)
+            } else {
+                (cur, line)
+            };
+            self.lines[node] = Some(kept);
+            line = carried;
+            if Self::eval(line, xl) < Self::eval(kept, xl) {
+                node *= 2;
+                r = mid;
+            } else {
+                node = 2 * node + 1;
+                l = mid;
+            }
+        }
+    }
+
+    fn normalize(&self, a: T, b: T) -> (T, T) {
+        if self.minimize {
+            (a, b)
+        } else {
+            (-a, -b)
+        }
+    }
+
+    // add y = ax + b over the whole domain
+    pub fn add_line(&mut self, a: T, b: T) {
+        let line = self.normalize(a, b);
+        self.add(1, 0, self.size, line);
+    }
+
+    // add y = ax + b only for x in [xl, xr)
+    pub fn add_segment(&mut self, xl: T, xr: T, a: T, b: T) {
+        let line = self.normalize(a, b);
+        let mut l = self.xs.lower_bound(xl) + self.size;
+        let mut r = self.xs.lower_bound(xr) + self.size;
+        // the width of the nodes at the current depth
+        let mut width = 1;
+        while l < r {
+            if l & 1 == 1 {
+                let lo = (l - self.size / width) * width;
+                self.add(l, lo, lo + width, line);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                let lo = (r - self.size / width) * width;
+                self.add(r, lo, lo + width, line);
+            }
+            l >>= 1;
+            r >>= 1;
+            width <<= 1;
+        }
+    }
+
+    // min (or max) of the lines at x, which must be one of the coordinates
+    pub fn query(&self, x: T) -> Option<T> {
+        let i = self.xs.lower_bound(x);
+        assert!(
+            i < self.xs.len() && self.xs[i] == x,
+            "x must be a coordinate"
+        );
+        let mut node = i + self.size;
+        let mut res: Option<T::Wide> = None;
+        while node > 0 {
+            if let Some(line) = self.lines[node] {
+                let y = Self::eval(line, x);
+                res = Some(res.map_or(y, |res| res.min(y)));
+            }
+            node >>= 1;
+        }
+        res.map(|y| T::narrow(if self.minimize { y } else { -y }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LiChaoTree;
+
+    #[test]
+    fn it_works() {
+        let xs: Vec<i64> = vec![-7, -3, 0, 2, 5, 11, 20];
+        let mut min = LiChaoTree::new(&xs, true);
+        let mut max = LiChaoTree::new(&xs, false);
+        assert_eq!(min.query(0), None);
+
+        let lines = [(3, -5), (-2, 4), (0, 1), (1, -1), (-1, 10), (5, -60)];
+        for (i, &(a, b)) in lines.iter().enumerate() {
+            min.add_line(a, b);
+            max.add_line(a, b);
+            for &x in &xs {
+                let values = lines[..=i].iter().map(|&(a, b)| a * x + b);
+                assert_eq!(min.query(x), values.clone().min());
+                assert_eq!(max.query(x), values.max());
+            }
+        }
+    }
+
+    #[test]
+    fn segment_test() {
+        let xs = (-10..10).collect::<Vec<i64>>();
+        let mut min = LiChaoTree::new(&xs, true);
+        let mut max = LiChaoTree::<i64>::new(&xs, false);
+        let segments = [
+            (-10, 10, 1, 0),
+            (-5, 3, -3, 2),
+            (0, 1, 0, -100),
+            (4, 8, 2, -20),
+            (-12, -6, -1, -15),
+            (7, 30, 0, 50),
+            (3, 3, 0, -1000),
+        ];
+        for (i, &(l, r, a, b)) in segments.iter().enumerate() {
+            min.add_segment(l, r, a, b);
+            max.add_segment(l, r, a, b);
+            for &x in &xs {
+                let values = segments[..=i]
+                    .iter()
+                    .filter(|&&(l, r, _, _)| l <= x && x < r)
+                    .map(|&(_, _, a, b)| a * x + b);
+                assert_eq!(min.query(x), values.clone().min());
+                assert_eq!(max.query(x), values.max());
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_test() {
+        const C: i64 = 5_000_000_000_000_000_000;
+        let xs = vec![
+            -4_000_000_000_000_000_000,
+            -1_000_000_000,
+            0,
+            1,
+            9_000_000_000,
+        ];
+        let lines = [(1, -C), (-1_000_000_000, C), (0, 0), (-1, C)];
+        let mut min = LiChaoTree::new(&xs, true);
+        for &(a, b) in &lines {
+            min.add_line(a, b);
+        }
+        for &x in &xs {
+            let expected = lines
+                .iter()
+                .map(|&(a, b)| a as i128 * x as i128 + b as i128)
+                .min()
+                .unwrap() as i64;
+            assert_eq!(min.query(x), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use crate::algorithms::binary_search::BinarySearch;
+use std::ops::{Bound, RangeBounds};
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    key: T,
+    // multiplicity of key
+    count: usize,
+    // the number of the elements in the subtree, with multiplicity
+    size: usize,
+    priority: u64,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+fn size<T>(node: &Link<T>) -> usize {
+    node.as_ref().map_or(0, |node| node.size)
+}
+
+fn update<T>(node: &mut Node<T>) {
+    node.size = size(&node.left) + node.count + size(&node.right);
+}
+
+// split into the keys k such that pred(k) and the rest, where pred must be monotone (true, ..., false)
+fn split<T, F>(node: Link<T>, pred: &F) -> (Link<T>, Link<T>)
+where
+    F: Fn(&T) -> bool,
+{
+    match node {
+        None => (None, None),
+        Some(mut node) => {
+            if pred(&node.key) {
+                let (l, r) = split(node.right.take(), pred);
+                node.right = l;
+                update(&mut node);
+                (Some(node), r)
+            } else {
+                let (l, r) = split(node.left.take(), pred);
+                node.left = r;
+                update(&mut node);
+                (l, Some(node))
+            }
+        }
+    }
+}
+
+fn merge<T>(l: Link<T>, r: Link<T>) -> Link<T> {
+    match (l, r) {
+        (None, r) => r,
+        (l, None) => l,
+        (Some(mut l), Some(mut r)) => {
+            if l.priority > r.priority {
+                l.right = merge(l.right.take(), Some(r));
+                update(&mut l);
+                Some(l)
+            } else {
+                r.left = merge(Some(l), r.left.take());
+                update(&mut r);
+                Some(r)
+            }
+        }
+    }
+}
+
+// Treap of the distinct keys with their multiplicity
+// insert, remove_one, count, rank, kth, lower_bound and upper_bound in expected O(log n)
+pub struct OrderedMultiSet<T> {
+    root: Link<T>,
+    seed: u64,
+}
+
+impl<T> OrderedMultiSet<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        OrderedMultiSet {
+            root: None,
+            seed: 88172645463325252,
+        }
+    }
+
+    fn next_priority(&mut self) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    // add delta to the multiplicity of x, which must be contained
+    fn add_count(node: &mut Link<T>, x: &T, delta: isize) {
+        let node = node.as_mut().unwrap();
+        if *x < node.key {
+            Self::add_count(&mut node.left, x, delta);
+        } else if node.key < *x {
+            Self::add_count(&mut node.right, x, delta);
+        } else {
+            node.count = node.count.wrapping_add_signed(delta);
+        }
+        update(node);
+    }
+
+    pub fn insert(&mut self, x: T) {
+        if self.count(&x) > 0 {
+            Self::add_count(&mut self.root, &x, 1);
+        } else {
+            let node = Some(Box::new(Node {
+                key: x,
+                count: 1,
+                size: 1,
+                priority: self.next_priority(),
+                left: None,
+                right: None,
+            }));
+            let x = &node.as_ref().unwrap().key;
+            let (l, r) = split(self.root.take(), &|k: &T| k < x);
+            self.root = merge(merge(l, node), r);
+        }
+    }
+
+    // remove one x, return false if x is not contained
+    pub fn remove_one(&mut self, x: &T) -> bool {
+        match self.count(x) {
+            0 => false,
+            1 => {
+                let (l, mr) = split(self.root.take(), &|k: &T| k < x);
+                let (_, r) = split(mr, &|k: &T| k <= x);
+                self.root = merge(l, r);
+                true
+            }
+            _ => {
+                Self::add_count(&mut self.root, x, -1);
+                true
+            }
+        }
+    }
+
+    // remove every x, and return how many were removed
+    pub fn remove_all(&mut self, x: &T) -> usize {
+        let (l, mr) = split(self.root.take(), &|k: &T| k < x);
+        let (m, r) = split(mr, &|k: &T| k <= x);
+        self.root = merge(l, r);
+        size(&m)
+    }
+
+    pub fn count(&self, x: &T) -> usize {
+        let mut node = &self.root;
+        while let Some(n) = node {
+            if *x < n.key {
+                node = &n.left;
+            } else if n.key < *x {
+                node = &n.right;
+            } else {
+                return n.count;
+            }
+        }
+        0
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        self.count(x) > 0
+    }
+
+    // the number of elements k such that pred(k), where pred is monotone (true, ..., false)
+    fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut res = 0;
+        let mut node = &self.root;
+        while let Some(n) = node {
+            if pred(&n.key) {
+                res += size(&n.left) + n.count;
+                node = &n.right;
+            } else {
+                node = &n.left;
+            }
+        }
+        res
+    }
+
+    // the number of elements less than x
+    pub fn rank(&self, x: &T) -> usize {
+        self.partition_point(|k| k < x)
+    }
+
+    // the k-th smallest element (0-based)
+    pub fn kth(&self, mut k: usize) -> Option<&T> {
+        let mut node = &self.root;
+        while let Some(n) = node {
+            let left = size(&n.left);
+            if k < left {
+                node = &n.left;
+            } else if k < left + n.count {
+                return Some(&n.key);
+            } else {
+                k -= left + n.count;
+                node = &n.right;
+            }
+        }
+        None
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.kth(0)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|k| self.kth(k))
+    }
+
+    // iterate over the elements with multiplicity in increasing order
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.range(..)
+    }
+
+    // iterate over the elements in range with multiplicity in increasing order
+    pub fn range<R>(&self, range: R) -> Iter<'_, T>
+    where
+        R: RangeBounds<T>,
+    {
+        let (l, start) = match range.start_bound() {
+            Bound::Included(x) => (self.partition_point(|k| k < x), Some((x, true))),
+            Bound::Excluded(x) => (self.partition_point(|k| k <= x), Some((x, false))),
+            Bound::Unbounded => (0, None),
+        };
+        let r = match range.end_bound() {
+            Bound::Included(x) => self.partition_point(|k| k <= x),
+            Bound::Excluded(x) => self.partition_point(|k| k < x),
+            Bound::Unbounded => self.len(),
+        };
+
+        // the nodes whose keys are in range and whose left subtrees have not been visited yet
+        let mut stack = vec![];
+        let mut node = &self.root;
+        while let Some(n) = node {
+            let in_range = match start {
+                Some((x, true)) => *x <= n.key,
+                Some((x, false)) => *x < n.key,
+                None => true,
+            };
+            if in_range {
+                stack.push(n.as_ref());
+                node = &n.left;
+            } else {
+                node = &n.right;
+            }
+        }
+
+        Iter {
+            stack,
+            rest: 0,
+            remaining: r.saturating_sub(l),
+        }
+    }
+}
+
+impl<T> Default for OrderedMultiSet<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BinarySearch<T> for OrderedMultiSet<T>
+where
+    T: Ord,
+{
+    // the number of elements less than key
+    fn lower_bound(&self, key: T) -> usize {
+        self.partition_point(|k| *k < key)
+    }
+
+    // the number of elements less than or equal to key
+    fn upper_bound(&self, key: T) -> usize {
+        self.partition_point(|k| *k <= key)
+    }
+}
+
+impl<T> FromIterator<T> for OrderedMultiSet<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut res = OrderedMultiSet::new();
+        for x in iter {
+            res.insert(x);
+        }
+        res
+    }
+}
+
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+    // how many more times the top of the stack is yielded
+    rest: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let top = *self.stack.last().unwrap();
+        if self.rest == 0 {
+            self.rest = top.count;
+        }
+        self.rest -= 1;
+        if self.rest == 0 {
+            self.stack.pop();
+            let mut node = &top.right;
+            while let Some(n) = node {
+                self.stack.push(n.as_ref());
+                node = &n.left;
+            }
+        }
+        Some(&top.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderedMultiSet;
+    use crate::algorithms::binary_search::BinarySearch;
+    use crate::xorshift::XorShift;
+
+    #[test]
+    fn it_works() {
+        let mut set = vec![5, 1, 3, 3, 9, 7, 3, 1]
+            .into_iter()
+            .collect::<OrderedMultiSet<i32>>();
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.count(&3), 3);
+        assert_eq!(set.count(&4), 0);
+        assert_eq!(set.rank(&3), 2);
+        assert_eq!(set.lower_bound(3), 2);
+        assert_eq!(set.upper_bound(3), 5);
+        assert_eq!(set.lower_bound(100), 8);
+        assert_eq!(set.kth(0), Some(&1));
+        assert_eq!(set.kth(4), Some(&3));
+        assert_eq!(set.kth(5), Some(&5));
+        assert_eq!(set.kth(8), None);
+        assert_eq!(set.min(), Some(&1));
+        assert_eq!(set.max(), Some(&9));
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![1, 1, 3, 3, 3, 5, 7, 9]
+        );
+        assert_eq!(
+            set.range(2..7).copied().collect::<Vec<_>>(),
+            vec![3, 3, 3, 5]
+        );
+        assert_eq!(
+            set.range(3..=7).copied().collect::<Vec<_>>(),
+            vec![3, 3, 3, 5, 7]
+        );
+        assert_eq!(set.range(..3).copied().collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(set.range(8..).copied().collect::<Vec<_>>(), vec![9]);
+        assert_eq!(set.range(4..5).count(), 0);
+
+        assert!(set.remove_one(&3));
+        assert!(!set.remove_one(&4));
+        assert_eq!(set.count(&3), 2);
+        assert!(set.remove_one(&5));
+        assert!(!set.contains(&5));
+        assert_eq!(set.remove_all(&1), 2);
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), vec![3, 3, 7, 9]);
+        assert_eq!(set.kth(2), Some(&7));
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::new(2463534242);
+
+        let mut set = OrderedMultiSet::new();
+        let mut naive: Vec<u64> = vec![];
+        for _ in 0..3000 {
+            let x = rng.rand(50);
+            match rng.rand(4) {
+                0 | 1 => {
+                    set.insert(x);
+                    let i = naive.partition_point(|&y| y < x);
+                    naive.insert(i, x);
+                }
+                2 => {
+                    let removed = set.remove_one(&x);
+                    let i = naive.partition_point(|&y| y < x);
+                    assert_eq!(removed, naive.get(i) == Some(&x));
+                    if removed {
+                        naive.remove(i);
+                    }
+                }
+                _ => {
+                    let k = rng.rand(naive.len() as u64 + 1) as usize;
+                    assert_eq!(set.kth(k), naive.get(k));
+                    assert_eq!(set.lower_bound(x), naive.lower_bound(x));
+                    assert_eq!(set.upper_bound(x), naive.upper_bound(x));
+                }
+            }
+        }
+        assert!(set.iter().eq(naive.iter()));
+    }
+}
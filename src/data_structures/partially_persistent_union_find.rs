@@ -0,0 +1,130 @@
+use std::mem;
+
+const INF: usize = usize::MAX;
+
+// Time t means the state right after the t-th call of union (1-based), t = 0 is the initial state.
+#[derive(Clone)]
+pub struct PartiallyPersistentUnionFind {
+    number_of_nodes: usize,
+    now: usize,
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    // time[x]: when x stopped being a root
+    time: Vec<usize>,
+    // (time, size) for every change of the size of the component rooted at x
+    size: Vec<Vec<(usize, usize)>>,
+}
+
+impl PartiallyPersistentUnionFind {
+    pub fn new(number_of_nodes: usize) -> Self {
+        PartiallyPersistentUnionFind {
+            number_of_nodes,
+            now: 0,
+            parent: (0..number_of_nodes).collect::<Vec<usize>>(),
+            rank: vec![0; number_of_nodes],
+            time: vec![INF; number_of_nodes],
+            size: vec![vec![(0, 1)]; number_of_nodes],
+        }
+    }
+
+    pub fn get_number_of_nodes(&self) -> usize {
+        self.number_of_nodes
+    }
+
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    // O(log n)
+    pub fn find(&self, mut x: usize, t: usize) -> usize {
+        while self.time[x] <= t {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn is_same(&self, x: usize, y: usize, t: usize) -> bool {
+        self.find(x, t) == self.find(y, t)
+    }
+
+    // return the time stamp of this union, which is counted even if x and y are already connected
+    pub fn union(&mut self, x: usize, y: usize) -> usize {
+        self.now += 1;
+        let (mut root_x, mut root_y) = (self.find(x, self.now), self.find(y, self.now));
+
+        if root_x != root_y {
+            if self.rank[root_x] < self.rank[root_y] {
+                mem::swap(&mut root_x, &mut root_y);
+            }
+
+            self.parent[root_y] = root_x;
+            self.time[root_y] = self.now;
+            let size = self.size[root_x].last().unwrap().1 + self.size[root_y].last().unwrap().1;
+            self.size[root_x].push((self.now, size));
+            if self.rank[root_x] == self.rank[root_y] {
+                self.rank[root_x] += 1;
+            }
+        }
+        self.now
+    }
+
+    // O(log n)
+    pub fn size(&self, x: usize, t: usize) -> usize {
+        let root = self.find(x, t);
+        let history = &self.size[root];
+        history[history.partition_point(|&(time, _)| time <= t) - 1].1
+    }
+
+    // the minimum t such that is_same(x, y, t), or None if x and y have never been connected
+    // O(log n)
+    pub fn first_connected_time(&self, mut x: usize, mut y: usize) -> Option<usize> {
+        let mut res = 0;
+        while x != y {
+            if self.time[x] > self.time[y] {
+                mem::swap(&mut x, &mut y);
+            }
+            if self.time[x] == INF {
+                return None;
+            }
+            res = self.time[x];
+            x = self.parent[x];
+        }
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartiallyPersistentUnionFind;
+
+    #[test]
+    fn it_works() {
+        let mut uf = PartiallyPersistentUnionFind::new(6);
+        assert_eq!(uf.union(0, 1), 1);
+        assert_eq!(uf.union(2, 3), 2);
+        assert_eq!(uf.union(1, 0), 3);
+        assert_eq!(uf.union(3, 4), 4);
+        assert_eq!(uf.union(0, 4), 5);
+        assert_eq!(uf.now(), 5);
+
+        assert!(!uf.is_same(0, 1, 0));
+        assert!(uf.is_same(0, 1, 1));
+        assert!(!uf.is_same(1, 4, 4));
+        assert!(uf.is_same(1, 4, 5));
+        assert!(uf.is_same(2, 4, 100));
+        assert!(!uf.is_same(0, 5, 100));
+
+        assert_eq!(uf.size(0, 0), 1);
+        assert_eq!(uf.size(0, 1), 2);
+        assert_eq!(uf.size(4, 3), 1);
+        assert_eq!(uf.size(4, 4), 3);
+        assert_eq!(uf.size(2, 5), 5);
+        assert_eq!(uf.size(5, 5), 1);
+
+        assert_eq!(uf.first_connected_time(0, 0), Some(0));
+        assert_eq!(uf.first_connected_time(1, 0), Some(1));
+        assert_eq!(uf.first_connected_time(2, 4), Some(4));
+        assert_eq!(uf.first_connected_time(1, 3), Some(5));
+        assert_eq!(uf.first_connected_time(1, 5), None);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::algebraic_structures::monoid::{AddMonoid, Monoid};
+use crate::algorithms::compress::Compress;
+
+#[derive(Clone, Copy)]
+struct Node<S> {
+    left: usize,
+    right: usize,
+    value: S,
+}
+
+// path-copying segment tree, every update creates a new version in O(log n)
+pub struct PersistentSegmentTree<M>
+where
+    M: Monoid,
+{
+    size: usize,
+    // nodes[0] is the node whose subtree consists only of M::id()
+    nodes: Vec<Node<M::S>>,
+    roots: Vec<usize>,
+}
+
+impl<M> PersistentSegmentTree<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    // version 0 consists of size copies of M::id()
+    pub fn new(size: usize) -> Self {
+        PersistentSegmentTree {
+            size,
+            nodes: vec![Node {
+                left: 0,
+                right: 0,
+                value: M::id(),
+            }],
+            roots: vec![0],
+        }
+    }
+
+    pub fn number_of_versions(&self) -> usize {
+        self.roots.len()
+    }
+
+    // create a new version in which a[idx] = x, and return its number
+    pub fn update(&mut self, version: usize, idx: usize, x: M::S) -> usize {
+        assert!(idx < self.size);
+        let root = self.update_rec(self.roots[version], 0, self.size, idx, x);
+        self.roots.push(root);
+        self.roots.len() - 1
+    }
+
+    fn update_rec(&mut self, node: usize, lo: usize, hi: usize, idx: usize, x: M::S) -> usize {
+        let new_node = if hi - lo == 1 {
+            Node {
+                left: 0,
+                right: 0,
+                value: x,
+            }
+        } else {
+            let Node { left, right, .. } = self.nodes[node];
+            let mid = (lo + hi) / 2;
+            let (left, right) = if idx < mid {
+                (self.update_rec(left, lo, mid, idx, x), right)
+            } else {
+                (left, self.update_rec(right, mid, hi, idx, x))
+            };
+            Node {
+                left,
+                right,
+                value: M::op(self.nodes[left].value, self.nodes[right].value),
+            }
+        };
+        self.nodes.push(new_node);
+        self.nodes.len() - 1
+    }
+
+    pub fn get(&self, version: usize, idx: usize) -> M::S {
+        self.fold(version, idx, idx + 1)
+    }
+
+    // return a[l] * ... * a[r - 1] in the version
+    pub fn fold(&self, version: usize, l: usize, r: usize) -> M::S {
+        assert!(l <= r && r <= self.size);
+        self.fold_rec(self.roots[version], 0, self.size, l, r)
+    }
+
+    fn fold_rec(&self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> M::S {
+        if node == 0 || r <= lo || hi <= l {
+            M::id()
+        } else if l <= lo && hi <= r {
+            self.nodes[node].value
+        } else {
+            let mid = (lo + hi) / 2;
+            M::op(
+                self.fold_rec(self.nodes[node].left, lo, mid, l, r),
+                self.fold_rec(self.nodes[node].right, mid, hi, l, r),
+            )
+        }
+    }
+}
+
+// k-th smallest value of a[l..r], using the versions of the prefixes of a
+pub struct RangeKthSmallest {
+    scale: Vec<isize>,
+    // version i counts the values of a[0..i]
+    tree: PersistentSegmentTree<AddMonoid>,
+}
+
+impl RangeKthSmallest {
+    pub fn new(a: &[isize]) -> RangeKthSmallest {
+        let compress = Compress::new(&[a]);
+        let mut tree = PersistentSegmentTree::<AddMonoid>::new(compress.len().max(1));
+        for (i, &c) in compress.coordinates[0].iter().enumerate() {
+            let count = tree.get(i, c);
+            tree.update(i, c, count + 1);
+        }
+        RangeKthSmallest {
+            scale: compress.scale,
+            tree,
+        }
+    }
+
+    // k is 0-based, None if k >= r - l
+    pub fn kth_smallest(&self, l: usize, r: usize, mut k: usize) -> Option<isize> {
+        if l >= r || k >= r - l {
+            return None;
+        }
+        let nodes = &self.tree.nodes;
+        let (mut node_l, mut node_r) = (self.tree.roots[l], self.tree.roots[r]);
+        let (mut lo, mut hi) = (0, self.tree.size);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let count = nodes[nodes[node_r].left].value - nodes[nodes[node_l].left].value;
+            if k < count {
+                node_l = nodes[node_l].left;
+                node_r = nodes[node_r].left;
+                hi = mid;
+            } else {
+                k -= count;
+                node_l = nodes[node_l].right;
+                node_r = nodes[node_r].right;
+                lo = mid;
+            }
+        }
+        Some(self.scale[lo])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PersistentSegmentTree, RangeKthSmallest};
+    use crate::algebraic_structures::monoid::{AddMonoid, MinMonoid};
+
+    #[test]
+    fn persistent_segment_tree_test() {
+        let mut seg = PersistentSegmentTree::<AddMonoid>::new(5);
+        let v1 = seg.update(0, 1, 3);
+        let v2 = seg.update(v1, 3, 4);
+        let v3 = seg.update(v1, 1, 10);
+        let v4 = seg.update(v2, 4, 1);
+        assert_eq!(seg.number_of_versions(), 5);
+
+        assert_eq!(seg.fold(0, 0, 5), 0);
+        assert_eq!(seg.fold(v1, 0, 5), 3);
+        assert_eq!(seg.fold(v2, 0, 5), 7);
+        assert_eq!(seg.fold(v2, 2, 4), 4);
+        assert_eq!(seg.fold(v3, 0, 5), 10);
+        assert_eq!(seg.fold(v4, 1, 5), 8);
+        assert_eq!(seg.fold(v4, 2, 2), 0);
+        assert_eq!(seg.get(v3, 1), 10);
+        assert_eq!(seg.get(v2, 1), 3);
+
+        let mut seg = PersistentSegmentTree::<MinMonoid>::new(3);
+        let v1 = seg.update(0, 0, 5);
+        let v2 = seg.update(v1, 2, 2);
+        assert_eq!(seg.fold(v1, 0, 3), 5);
+        assert_eq!(seg.fold(v2, 0, 2), 5);
+        assert_eq!(seg.fold(v2, 0, 3), 2);
+        assert_eq!(seg.fold(v2, 1, 2), usize::MAX);
+    }
+
+    #[test]
+    fn kth_smallest_test() {
+        let a = vec![5, -1, 3, 3, 8, 0, -1];
+        let kth = RangeKthSmallest::new(&a);
+        for l in 0..a.len() {
+            for r in l..=a.len() {
+                let mut b = a[l..r].to_vec();
+                b.sort();
+                for k in 0..=b.len() {
+                    assert_eq!(kth.kth_smallest(l, r, k), b.get(k).copied());
+                }
+            }
+        }
+    }
+}
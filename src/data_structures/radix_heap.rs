@@ -0,0 +1,108 @@
+// Min-heap for monotone integer keys, that is, a pushed key must not be less than the last popped key
+// push in O(1) and pop in amortized O(log C), where C is the maximum key
+pub struct RadixHeap<V> {
+    // buckets[i] holds the keys whose highest bit differing from last is the (i - 1)-th
+    buckets: Vec<Vec<(usize, V)>>,
+    // the last popped key
+    last: usize,
+    len: usize,
+}
+
+impl<V> RadixHeap<V> {
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: (0..=usize::BITS).map(|_| vec![]).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(&self, key: usize) -> usize {
+        (usize::BITS - (key ^ self.last).leading_zeros()) as usize
+    }
+
+    pub fn push(&mut self, key: usize, value: V) {
+        assert!(key >= self.last, "keys must be monotone");
+        let i = self.bucket(key);
+        self.buckets[i].push((key, value));
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, V)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            let bucket = std::mem::take(&mut self.buckets[i]);
+            self.last = bucket.iter().map(|&(key, _)| key).min().unwrap();
+            for (key, value) in bucket {
+                let j = self.bucket(key);
+                self.buckets[j].push((key, value));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+impl<V> Default for RadixHeap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RadixHeap;
+    use crate::xorshift::XorShift;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    #[test]
+    fn it_works() {
+        let mut heap = RadixHeap::new();
+        heap.push(5, 'a');
+        heap.push(3, 'b');
+        heap.push(10, 'c');
+        assert_eq!(heap.pop(), Some((3, 'b')));
+        heap.push(3, 'd');
+        heap.push(usize::MAX, 'e');
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.pop(), Some((3, 'd')));
+        assert_eq!(heap.pop(), Some((5, 'a')));
+        assert_eq!(heap.pop(), Some((10, 'c')));
+        assert_eq!(heap.pop(), Some((usize::MAX, 'e')));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let mut heap = RadixHeap::new();
+        let mut naive = BinaryHeap::new();
+        let mut last = 0;
+        for _ in 0..3000 {
+            if rng.rand(3) == 0 {
+                let res = heap.pop().map(|(key, _)| key);
+                assert_eq!(res, naive.pop().map(|Reverse(key)| key));
+                if let Some(key) = res {
+                    last = key;
+                }
+            } else {
+                let key = last + rng.rand(1000) as usize;
+                heap.push(key, ());
+                naive.push(Reverse(key));
+            }
+            assert_eq!(heap.len(), naive.len());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    algebraic_structures::{commutativity::Commutativity, group::Group},
+    data_structures::fenwick_tree::FenwickTree,
+};
+
+// Segment tree over the x-coordinates of a fixed set of points, each node having the sorted
+// y-coordinates of its points and a FenwickTree over them.
+// The coordinates can be arbitrary, and the memory is O(n log n).
+pub struct RangeTree<G>
+where
+    G: Group + Commutativity,
+{
+    size: usize,
+    xs: Vec<isize>,
+    ys: Vec<Vec<isize>>,
+    fenwick_trees: Vec<FenwickTree<G>>,
+}
+
+impl<G> RangeTree<G>
+where
+    G: Group + Commutativity,
+    G::S: Clone + Copy,
+{
+    // every point has the weight G::id() at first
+    pub fn new(points: &[(isize, isize)]) -> Self {
+        let mut xs = points.iter().map(|&(x, _)| x).collect::<Vec<_>>();
+        xs.sort();
+        xs.dedup();
+        let size = xs.len().next_power_of_two();
+
+        let mut ys = vec![vec![]; 2 * size];
+        for &(x, y) in points {
+            ys[size + xs.partition_point(|&a| a < x)].push(y);
+        }
+        for i in (1..2 * size).rev() {
+            if i < size {
+                ys[i] = [ys[2 * i].clone(), ys[2 * i + 1].clone()].concat();
+            }
+            ys[i].sort();
+            ys[i].dedup();
+        }
+        let fenwick_trees = ys.iter().map(|ys| FenwickTree::new(ys.len())).collect();
+
+        RangeTree {
+            size,
+            xs,
+            ys,
+            fenwick_trees,
+        }
+    }
+
+    // the weight of (x, y) += w, where (x, y) must be one of the points given to new
+    // O(log^2 n)
+    pub fn add(&mut self, x: isize, y: isize, w: G::S) {
+        let i = self.xs.binary_search(&x).expect("not registered");
+        let mut i = i + self.size;
+        while i > 0 {
+            let j = self.ys[i].binary_search(&y).expect("not registered");
+            self.fenwick_trees[i].add(j, w);
+            i /= 2;
+        }
+    }
+
+    fn sum_node(&self, i: usize, y1: isize, y2: isize) -> G::S {
+        let l = self.ys[i].partition_point(|&y| y < y1);
+        let r = self.ys[i].partition_point(|&y| y < y2);
+        if l < r {
+            self.fenwick_trees[i].sum(l..r)
+        } else {
+            G::id()
+        }
+    }
+
+    // the sum of the weights of the points such that x1 <= x < x2 and y1 <= y < y2
+    // O(log^2 n)
+    pub fn sum(&self, x1: isize, x2: isize, y1: isize, y2: isize) -> G::S {
+        let mut l = self.xs.partition_point(|&x| x < x1) + self.size;
+        let mut r = self.xs.partition_point(|&x| x < x2) + self.size;
+        let mut res = G::id();
+        while l < r {
+            if l % 2 == 1 {
+                res = G::op(res, self.sum_node(l, y1, y2));
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                res = G::op(res, self.sum_node(r, y1, y2));
+            }
+            l /= 2;
+            r /= 2;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeTree;
+    use crate::algebraic_structures::group::AddGroup;
+
+    #[test]
+    fn it_works() {
+        let points = vec![
+            (1_000_000_000, 5),
+            (-3, -1_000_000_000),
+            (7, 7),
+            (7, 2),
+            (0, 0),
+            (7, 7),
+        ];
+        let mut range_tree = RangeTree::<AddGroup>::new(&points);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            range_tree.add(x, y, i as i64 + 1);
+        }
+        assert_eq!(
+            range_tree.sum(-2_000_000_000, 2_000_000_000, -2_000_000_000, 2_000_000_000),
+            21
+        );
+        assert_eq!(range_tree.sum(7, 8, 0, 10), 13);
+        assert_eq!(range_tree.sum(7, 8, 0, 7), 4);
+        assert_eq!(range_tree.sum(-3, 8, -1_000_000_000, 1), 7);
+        assert_eq!(range_tree.sum(0, 1_000_000_001, 5, 6), 1);
+        assert_eq!(range_tree.sum(1, 7, -100, 100), 0);
+        assert_eq!(range_tree.sum(5, 1, -100, 100), 0);
+
+        range_tree.add(0, 0, -5);
+        assert_eq!(range_tree.sum(-10, 10, -10, 10), 13);
+    }
+}
@@ -0,0 +1,130 @@
+use std::mem;
+
+// union by size without path compression, so that every union can be undone
+#[derive(Clone)]
+pub struct RollbackUnionFind {
+    number_of_nodes: usize,
+    number_of_connected_components: usize,
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    // (surviving root, absorbed root) for each call of union, None if nothing was merged
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(number_of_nodes: usize) -> Self {
+        RollbackUnionFind {
+            number_of_nodes,
+            number_of_connected_components: number_of_nodes,
+            parent: (0..number_of_nodes).collect::<Vec<usize>>(),
+            size: vec![1; number_of_nodes],
+            history: vec![],
+        }
+    }
+
+    pub fn get_number_of_connected_components(&self) -> usize {
+        self.number_of_connected_components
+    }
+
+    pub fn get_number_of_nodes(&self) -> usize {
+        self.number_of_nodes
+    }
+
+    pub fn is_root(&self, x: usize) -> bool {
+        self.parent[x] == x
+    }
+
+    // O(log n)
+    pub fn find(&self, mut x: usize) -> usize {
+        while !self.is_root(x) {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn is_same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut root_x, mut root_y) = (self.find(x), self.find(y));
+
+        if root_x == root_y {
+            self.history.push(None);
+            return false;
+        }
+
+        if self.size[root_x] < self.size[root_y] {
+            mem::swap(&mut root_x, &mut root_y);
+        }
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        self.number_of_connected_components -= 1;
+        self.history.push(Some((root_x, root_y)));
+        true
+    }
+
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    // cancel the last call of union
+    // return false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Some((root_x, root_y))) => {
+                self.parent[root_y] = root_y;
+                self.size[root_x] -= self.size[root_y];
+                self.number_of_connected_components += 1;
+                true
+            }
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    // the number of calls of union so far, to be passed to rollback
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    // undo every union called after the snapshot was taken
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollbackUnionFind;
+
+    #[test]
+    fn it_works() {
+        let mut uf = RollbackUnionFind::new(5);
+        assert!(uf.union(0, 1));
+        let snapshot = uf.snapshot();
+
+        assert!(uf.union(1, 2));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(3, 4));
+        assert!(uf.is_same(0, 2));
+        assert_eq!(uf.size(2), 3);
+        assert_eq!(uf.get_number_of_connected_components(), 2);
+
+        assert!(uf.undo());
+        assert!(!uf.is_same(3, 4));
+        assert!(uf.is_same(0, 2));
+
+        uf.rollback(snapshot);
+        assert!(uf.is_same(0, 1));
+        assert!(!uf.is_same(0, 2));
+        assert_eq!(uf.size(0), 2);
+        assert_eq!(uf.get_number_of_connected_components(), 4);
+
+        uf.rollback(0);
+        assert!(!uf.is_same(0, 1));
+        assert!(!uf.undo());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::algebraic_structures::{commutativity::Commutativity, monoid::Monoid};
+
+// point update, rectangle fold on an H x W grid
+pub struct SegmentTree2D<M>
+where
+    M: Monoid + Commutativity,
+{
+    height: usize,
+    width: usize,
+    // 1-based heap in both directions, the leaves are data[height + i][width + j]
+    data: Vec<Vec<M::S>>,
+}
+
+impl<M> SegmentTree2D<M>
+where
+    M: Monoid + Commutativity,
+    M::S: Clone + Copy,
+{
+    pub fn new(height: usize, width: usize) -> Self {
+        let height = height.next_power_of_two();
+        let width = width.next_power_of_two();
+        SegmentTree2D {
+            height,
+            width,
+            data: vec![vec![M::id(); 2 * width]; 2 * height],
+        }
+    }
+
+    // O(log H log W)
+    pub fn update(&mut self, i: usize, j: usize, x: M::S) {
+        let mut i = i + self.height;
+        let j = j + self.width;
+        self.data[i][j] = x;
+        let mut k = j / 2;
+        while k > 0 {
+            self.data[i][k] = M::op(self.data[i][2 * k], self.data[i][2 * k + 1]);
+            k /= 2;
+        }
+        i /= 2;
+        while i > 0 {
+            let mut k = j;
+            while k > 0 {
+                self.data[i][k] = M::op(self.data[2 * i][k], self.data[2 * i + 1][k]);
+                k /= 2;
+            }
+            i /= 2;
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> M::S {
+        self.data[i + self.height][j + self.width]
+    }
+
+    fn fold_row(&self, i: usize, mut l: usize, mut r: usize) -> M::S {
+        l += self.width;
+        r += self.width;
+        let mut res = M::id();
+        while l < r {
+            if l % 2 == 1 {
+                res = M::op(res, self.data[i][l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                res = M::op(res, self.data[i][r]);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        res
+    }
+
+    // fold of a[i][j] for r0 <= i < r1, c0 <= j < c1
+    // O(log H log W)
+    pub fn fold(&self, mut r0: usize, mut r1: usize, c0: usize, c1: usize) -> M::S {
+        r0 += self.height;
+        r1 += self.height;
+        let mut res = M::id();
+        while r0 < r1 {
+            if r0 % 2 == 1 {
+                res = M::op(res, self.fold_row(r0, c0, c1));
+                r0 += 1;
+            }
+            if r1 % 2 == 1 {
+                r1 -= 1;
+                res = M::op(res, self.fold_row(r1, c0, c1));
+            }
+            r0 /= 2;
+            r1 /= 2;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegmentTree2D;
+    use crate::algebraic_structures::monoid::{AddMonoid, MinMonoid};
+
+    #[test]
+    fn it_works() {
+        let grid = [[3, 1, 4, 1, 5], [9, 2, 6, 5, 3], [5, 8, 9, 7, 9]];
+        let mut seg_add = SegmentTree2D::<AddMonoid>::new(3, 5);
+        let mut seg_min = SegmentTree2D::<MinMonoid>::new(3, 5);
+        for (i, row) in grid.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                seg_add.update(i, j, x);
+                seg_min.update(i, j, x);
+            }
+        }
+        for r0 in 0..3 {
+            for r1 in r0 + 1..=3 {
+                for c0 in 0..5 {
+                    for c1 in c0 + 1..=5 {
+                        let cells = grid[r0..r1].iter().flat_map(|row| row[c0..c1].iter());
+                        assert_eq!(seg_add.fold(r0, r1, c0, c1), cells.clone().sum::<usize>());
+                        assert_eq!(seg_min.fold(r0, r1, c0, c1), *cells.min().unwrap());
+                    }
+                }
+            }
+        }
+
+        seg_add.update(1, 1, 100);
+        assert_eq!(seg_add.get(1, 1), 100);
+        assert_eq!(seg_add.fold(0, 2, 0, 2), 113);
+        assert_eq!(seg_add.fold(1, 1, 0, 5), 0);
+    }
+}
@@ -0,0 +1,319 @@
+const INF: i64 = i64::MAX / 4;
+
+#[derive(Clone, Copy)]
+struct Node {
+    len: i64,
+    sum: i64,
+    max1: i64,
+    max2: i64,
+    max_cnt: i64,
+    min1: i64,
+    min2: i64,
+    min_cnt: i64,
+    add: i64,
+}
+
+impl Node {
+    fn empty() -> Node {
+        Node {
+            len: 0,
+            sum: 0,
+            max1: -INF,
+            max2: -INF,
+            max_cnt: 0,
+            min1: INF,
+            min2: INF,
+            min_cnt: 0,
+            add: 0,
+        }
+    }
+
+    fn leaf(x: i64) -> Node {
+        Node {
+            len: 1,
+            sum: x,
+            max1: x,
+            max2: -INF,
+            max_cnt: 1,
+            min1: x,
+            min2: INF,
+            min_cnt: 1,
+            add: 0,
+        }
+    }
+
+    fn merge(l: &Node, r: &Node) -> Node {
+        let (max1, max2, max_cnt) = if l.max1 == r.max1 {
+            (l.max1, l.max2.max(r.max2), l.max_cnt + r.max_cnt)
+        } else if l.max1 > r.max1 {
+            (l.max1, l.max2.max(r.max1), l.max_cnt)
+        } else {
+            (r.max1, l.max1.max(r.max2), r.max_cnt)
+        };
+        let (min1, min2, min_cnt) = if l.min1 == r.min1 {
+            (l.min1, l.min2.min(r.min2), l.min_cnt + r.min_cnt)
+        } else if l.min1 < r.min1 {
+            (l.min1, l.min2.min(r.min1), l.min_cnt)
+        } else {
+            (r.min1, l.min1.min(r.min2), r.min_cnt)
+        };
+        Node {
+            len: l.len + r.len,
+            sum: l.sum + r.sum,
+            max1,
+            max2,
+            max_cnt,
+            min1,
+            min2,
+            min_cnt,
+            add: 0,
+        }
+    }
+
+    fn apply_add(&mut self, x: i64) {
+        if self.len == 0 {
+            return;
+        }
+        self.sum += x * self.len;
+        self.max1 += x;
+        if self.max2 != -INF {
+            self.max2 += x;
+        }
+        self.min1 += x;
+        if self.min2 != INF {
+            self.min2 += x;
+        }
+        self.add += x;
+    }
+
+    // requires max2 < x < max1
+    fn apply_chmin(&mut self, x: i64) {
+        self.sum += (x - self.max1) * self.max_cnt;
+        if self.max1 == self.min1 {
+            self.min1 = x;
+        } else if self.max1 == self.min2 {
+            self.min2 = x;
+        }
+        self.max1 = x;
+    }
+
+    // requires min1 < x < min2
+    fn apply_chmax(&mut self, x: i64) {
+        self.sum += (x - self.min1) * self.min_cnt;
+        if self.min1 == self.max1 {
+            self.max1 = x;
+        } else if self.min1 == self.max2 {
+            self.max2 = x;
+        }
+        self.min1 = x;
+    }
+}
+
+// Segment tree beats (Ji driver segment tree)
+// range chmin / chmax / add / assign, range sum / min / max in amortized O(log^2 n)
+pub struct SegmentTreeBeats {
+    size: usize,
+    // 1-based heap
+    data: Vec<Node>,
+}
+
+impl SegmentTreeBeats {
+    pub fn new(a: &[i64]) -> SegmentTreeBeats {
+        let size = a.len().next_power_of_two();
+        let mut data = vec![Node::empty(); 2 * size];
+        for (i, &x) in a.iter().enumerate() {
+            data[size + i] = Node::leaf(x);
+        }
+        for k in (1..size).rev() {
+            data[k] = Node::merge(&data[2 * k], &data[2 * k + 1]);
+        }
+        SegmentTreeBeats { size, data }
+    }
+
+    fn pull(&mut self, k: usize) {
+        self.data[k] = Node::merge(&self.data[2 * k], &self.data[2 * k + 1]);
+    }
+
+    fn propagate(&mut self, k: usize) {
+        let Node {
+            max1, min1, add, ..
+        } = self.data[k];
+        for c in [2 * k, 2 * k + 1] {
+            if add != 0 {
+                self.data[c].apply_add(add);
+            }
+            if self.data[c].max1 > max1 {
+                self.data[c].apply_chmin(max1);
+            }
+            if self.data[c].min1 < min1 {
+                self.data[c].apply_chmax(min1);
+            }
+        }
+        self.data[k].add = 0;
+    }
+
+    // a[i] = min(a[i], x) for l <= i < r
+    pub fn chmin(&mut self, l: usize, r: usize, x: i64) {
+        self.chmin_rec(1, 0, self.size, l, r, x);
+    }
+
+    fn chmin_rec(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l || self.data[k].max1 <= x {
+            return;
+        }
+        if l <= lo && hi <= r && self.data[k].max2 < x {
+            self.data[k].apply_chmin(x);
+            return;
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        self.chmin_rec(2 * k, lo, mid, l, r, x);
+        self.chmin_rec(2 * k + 1, mid, hi, l, r, x);
+        self.pull(k);
+    }
+
+    // a[i] = max(a[i], x) for l <= i < r
+    pub fn chmax(&mut self, l: usize, r: usize, x: i64) {
+        self.chmax_rec(1, 0, self.size, l, r, x);
+    }
+
+    fn chmax_rec(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l || self.data[k].min1 >= x {
+            return;
+        }
+        if l <= lo && hi <= r && self.data[k].min2 > x {
+            self.data[k].apply_chmax(x);
+            return;
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        self.chmax_rec(2 * k, lo, mid, l, r, x);
+        self.chmax_rec(2 * k + 1, mid, hi, l, r, x);
+        self.pull(k);
+    }
+
+    // a[i] += x for l <= i < r
+    pub fn add(&mut self, l: usize, r: usize, x: i64) {
+        self.add_rec(1, 0, self.size, l, r, x);
+    }
+
+    fn add_rec(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self.data[k].apply_add(x);
+            return;
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        self.add_rec(2 * k, lo, mid, l, r, x);
+        self.add_rec(2 * k + 1, mid, hi, l, r, x);
+        self.pull(k);
+    }
+
+    // a[i] = x for l <= i < r
+    pub fn assign(&mut self, l: usize, r: usize, x: i64) {
+        self.chmin(l, r, x);
+        self.chmax(l, r, x);
+    }
+
+    fn fold(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize) -> Node {
+        if r <= lo || hi <= l {
+            return Node::empty();
+        }
+        if l <= lo && hi <= r {
+            return self.data[k];
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        Node::merge(
+            &self.fold(2 * k, lo, mid, l, r),
+            &self.fold(2 * k + 1, mid, hi, l, r),
+        )
+    }
+
+    pub fn sum(&mut self, l: usize, r: usize) -> i64 {
+        self.fold(1, 0, self.size, l, r).sum
+    }
+
+    // None if l == r
+    pub fn min(&mut self, l: usize, r: usize) -> Option<i64> {
+        let node = self.fold(1, 0, self.size, l, r);
+        if node.len == 0 {
+            None
+        } else {
+            Some(node.min1)
+        }
+    }
+
+    // None if l == r
+    pub fn max(&mut self, l: usize, r: usize) -> Option<i64> {
+        let node = self.fold(1, 0, self.size, l, r);
+        if node.len == 0 {
+            None
+        } else {
+            Some(node.max1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegmentTreeBeats;
+    use crate::xorshift::XorShift;
+
+    #[test]
+    fn it_works() {
+        let mut seg = SegmentTreeBeats::new(&[5, 1, 4, 2, 3]);
+        assert_eq!(seg.sum(0, 5), 15);
+        seg.chmin(0, 5, 3);
+        assert_eq!(seg.sum(0, 5), 12);
+        seg.chmax(1, 4, 3);
+        assert_eq!(seg.sum(0, 5), 15);
+        assert_eq!(seg.min(0, 5), Some(3));
+        seg.add(2, 5, -4);
+        assert_eq!(seg.min(0, 5), Some(-1));
+        assert_eq!(seg.max(2, 5), Some(-1));
+        assert_eq!(seg.sum(0, 5), 3);
+        seg.assign(1, 3, 10);
+        assert_eq!(seg.max(0, 5), Some(10));
+        assert_eq!(seg.sum(0, 5), 21);
+        assert_eq!(seg.min(3, 3), None);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let n = 13;
+        let mut naive = (0..n).map(|_| rng.rand(21) as i64 - 10).collect::<Vec<_>>();
+        let mut seg = SegmentTreeBeats::new(&naive);
+        for _ in 0..2000 {
+            let l = rng.rand(n as u64) as usize;
+            let r = l + 1 + rng.rand((n - l) as u64) as usize;
+            let x = rng.rand(21) as i64 - 10;
+            match rng.rand(7) {
+                0 => {
+                    seg.chmin(l, r, x);
+                    naive[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+                }
+                1 => {
+                    seg.chmax(l, r, x);
+                    naive[l..r].iter_mut().for_each(|a| *a = (*a).max(x));
+                }
+                2 => {
+                    seg.add(l, r, x);
+                    naive[l..r].iter_mut().for_each(|a| *a += x);
+                }
+                3 => {
+                    seg.assign(l, r, x);
+                    naive[l..r].iter_mut().for_each(|a| *a = x);
+                }
+                4 => assert_eq!(seg.sum(l, r), naive[l..r].iter().sum::<i64>()),
+                5 => assert_eq!(seg.min(l, r), naive[l..r].iter().min().copied()),
+                _ => assert_eq!(seg.max(l, r), naive[l..r].iter().max().copied()),
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::algebraic_structures::monoid::Monoid;
+
+// FIFO queue with the fold of all the elements, every operation in amortized O(1)
+pub struct SlidingWindowAggregation<M>
+where
+    M: Monoid,
+{
+    // (x, x * ... * the last element of front), the top is the first element of the queue
+    front: Vec<(M::S, M::S)>,
+    back: Vec<M::S>,
+    // the fold of back
+    back_fold: M::S,
+}
+
+impl<M> SlidingWindowAggregation<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    pub fn new() -> Self {
+        SlidingWindowAggregation {
+            front: vec![],
+            back: vec![],
+            back_fold: M::id(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&mut self, x: M::S) {
+        self.back.push(x);
+        self.back_fold = M::op(self.back_fold, x);
+    }
+
+    pub fn pop(&mut self) -> Option<M::S> {
+        if self.front.is_empty() {
+            let mut fold = M::id();
+            while let Some(x) = self.back.pop() {
+                fold = M::op(x, fold);
+                self.front.push((x, fold));
+            }
+            self.back_fold = M::id();
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    // the fold of the elements from the first to the last
+    pub fn fold(&self) -> M::S {
+        let front = self.front.last().map_or(M::id(), |&(_, fold)| fold);
+        M::op(front, self.back_fold)
+    }
+}
+
+impl<M> Default for SlidingWindowAggregation<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Deque with the fold of all the elements, every operation in amortized O(1)
+pub struct SlidingWindowAggregationDeque<M>
+where
+    M: Monoid,
+{
+    // (x, x * ... * the last element of front), the top is the first element of the deque
+    front: Vec<(M::S, M::S)>,
+    // (x, the first element of back * ... * x), the top is the last element of the deque
+    back: Vec<(M::S, M::S)>,
+}
+
+impl<M> SlidingWindowAggregationDeque<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    pub fn new() -> Self {
+        SlidingWindowAggregationDeque {
+            front: vec![],
+            back: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_front(&mut self, x: M::S) {
+        let fold = self.front.last().map_or(M::id(), |&(_, fold)| fold);
+        self.front.push((x, M::op(x, fold)));
+    }
+
+    pub fn push_back(&mut self, x: M::S) {
+        let fold = self.back.last().map_or(M::id(), |&(_, fold)| fold);
+        self.back.push((x, M::op(fold, x)));
+    }
+
+    // rebuild both stacks so that the first mid elements are in front
+    fn rebalance(&mut self, mid: usize) {
+        let elements = self
+            .front
+            .drain(..)
+            .rev()
+            .chain(self.back.drain(..))
+            .map(|(x, _)| x)
+            .collect::<Vec<_>>();
+        for &x in elements[..mid].iter().rev() {
+            self.push_front(x);
+        }
+        for &x in &elements[mid..] {
+            self.push_back(x);
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<M::S> {
+        if self.front.is_empty() {
+            self.rebalance(self.back.len().div_ceil(2));
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    pub fn pop_back(&mut self) -> Option<M::S> {
+        if self.back.is_empty() {
+            self.rebalance(self.front.len() / 2);
+        }
+        self.back.pop().map(|(x, _)| x)
+    }
+
+    pub fn front(&self) -> Option<M::S> {
+        self.front
+            .last()
+            .or_else(|| self.back.first())
+            .map(|&(x, _)| x)
+    }
+
+    pub fn back(&self) -> Option<M::S> {
+        self.back
+            .last()
+            .or_else(|| self.front.first())
+            .map(|&(x, _)| x)
+    }
+
+    // the fold of the elements from the first to the last
+    pub fn fold(&self) -> M::S {
+        let front = self.front.last().map_or(M::id(), |&(_, fold)| fold);
+        let back = self.back.last().map_or(M::id(), |&(_, fold)| fold);
+        M::op(front, back)
+    }
+}
+
+impl<M> Default for SlidingWindowAggregationDeque<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SlidingWindowAggregation, SlidingWindowAggregationDeque};
+    use crate::algebraic_structures::{
+        algebraical::Algebraical,
+        identity::Identity,
+        magma::Magma,
+        monoid::{GcdMonoid, MinMonoid, Monoid},
+        semigroup::SemiGroup,
+    };
+    use crate::xorshift::XorShift;
+    use std::collections::VecDeque;
+
+    // composition of x -> ax + b, where op(f, g) applies f first
+    struct Affine;
+
+    impl Algebraical for Affine {
+        type S = (i64, i64);
+    }
+
+    impl Magma for Affine {
+        fn op(f: Self::S, g: Self::S) -> Self::S {
+            (f.0 * g.0 % 998244353, (f.1 * g.0 + g.1) % 998244353)
+        }
+    }
+
+    impl Identity for Affine {
+        fn id() -> Self::S {
+            (1, 0)
+        }
+    }
+
+    impl SemiGroup for Affine {}
+
+    impl Monoid for Affine {}
+
+    fn naive(a: &VecDeque<(i64, i64)>) -> (i64, i64) {
+        a.iter().fold(Affine::id(), |acc, &f| Affine::op(acc, f))
+    }
+
+    #[test]
+    fn it_works() {
+        let a = [5, 3, 8, 1, 9, 2, 7];
+        let mut swag = SlidingWindowAggregation::<MinMonoid>::new();
+        let mut res = vec![];
+        for (i, &x) in a.iter().enumerate() {
+            swag.push(x);
+            if i >= 3 {
+                swag.pop();
+            }
+            res.push(swag.fold());
+        }
+        assert_eq!(res, vec![5, 3, 3, 1, 1, 1, 2]);
+        assert_eq!(swag.len(), 3);
+
+        let mut swag = SlidingWindowAggregation::<GcdMonoid>::new();
+        assert_eq!(swag.pop(), None);
+        for x in [12, 18, 30] {
+            swag.push(x);
+        }
+        assert_eq!(swag.fold(), 6);
+        assert_eq!(swag.pop(), Some(12));
+        swag.push(45);
+        assert_eq!(swag.fold(), 3);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let mut queue = SlidingWindowAggregation::<Affine>::new();
+        let mut deque = SlidingWindowAggregationDeque::<Affine>::new();
+        let mut naive_queue = VecDeque::new();
+        let mut naive_deque = VecDeque::new();
+        for _ in 0..3000 {
+            let f = (rng.rand(100) as i64, rng.rand(100) as i64);
+            match rng.rand(5) {
+                0 | 1 => {
+                    queue.push(f);
+                    naive_queue.push_back(f);
+                    deque.push_back(f);
+                    naive_deque.push_back(f);
+                }
+                2 => {
+                    assert_eq!(queue.pop(), naive_queue.pop_front());
+                    deque.push_front(f);
+                    naive_deque.push_front(f);
+                }
+                3 => {
+                    assert_eq!(deque.pop_front(), naive_deque.pop_front());
+                }
+                _ => {
+                    assert_eq!(deque.pop_back(), naive_deque.pop_back());
+                }
+            }
+            assert_eq!(queue.fold(), naive(&naive_queue));
+            assert_eq!(queue.len(), naive_queue.len());
+            assert_eq!(deque.fold(), naive(&naive_deque));
+            assert_eq!(deque.len(), naive_deque.len());
+            assert_eq!(deque.front(), naive_deque.front().copied());
+            assert_eq!(deque.back(), naive_deque.back().copied());
+        }
+    }
+}
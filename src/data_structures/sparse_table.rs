@@ -0,0 +1,71 @@
+use crate::algebraic_structures::{idempotence::Idempotence, semigroup::SemiGroup};
+
+// static range fold in O(1) for idempotent semigroups, built in O(n log n)
+pub struct SparseTable<S>
+where
+    S: SemiGroup + Idempotence,
+{
+    // table[k][i] = a[i] * ... * a[i + 2^k - 1]
+    table: Vec<Vec<S::S>>,
+}
+
+impl<S> SparseTable<S>
+where
+    S: SemiGroup + Idempotence,
+    S::S: Clone + Copy,
+{
+    pub fn new(a: &[S::S]) -> Self {
+        let mut table = vec![a.to_vec()];
+        let mut k = 1;
+        while 1 << k <= a.len() {
+            let prev = &table[k - 1];
+            let row = (0..=a.len() - (1 << k))
+                .map(|i| S::op(prev[i], prev[i + (1 << (k - 1))]))
+                .collect();
+            table.push(row);
+            k += 1;
+        }
+        SparseTable { table }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // return a[l] * ... * a[r - 1], where l < r
+    pub fn fold(&self, l: usize, r: usize) -> S::S {
+        assert!(l < r && r <= self.len());
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        S::op(self.table[k][l], self.table[k][r - (1 << k)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseTable;
+    use crate::algebraic_structures::monoid::{GcdMonoid, MaxMonoid, MinMonoid};
+
+    #[test]
+    fn it_works() {
+        let a = vec![12, 18, 7, 30, 42, 6, 9, 3, 27, 15];
+        let min = SparseTable::<MinMonoid>::new(&a);
+        let max = SparseTable::<MaxMonoid>::new(&a);
+        let gcd = SparseTable::<GcdMonoid>::new(&a);
+        assert_eq!(min.len(), 10);
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                assert_eq!(min.fold(l, r), *a[l..r].iter().min().unwrap());
+                assert_eq!(max.fold(l, r), *a[l..r].iter().max().unwrap());
+            }
+        }
+        assert_eq!(gcd.fold(0, 2), 6);
+        assert_eq!(gcd.fold(3, 6), 6);
+        assert_eq!(gcd.fold(5, 9), 3);
+        assert_eq!(gcd.fold(0, 10), 1);
+        assert_eq!(gcd.fold(2, 3), 7);
+    }
+}
@@ -0,0 +1,193 @@
+use std::ops::Add;
+
+// Array split into blocks, each of which keeps a sorted copy and a lazy added value
+// range_add and set in O(B log B + n / B), range_count_ge in O(B + (n / B) log B)
+pub struct BlockArray<T> {
+    block_size: usize,
+    // the values without the lazy values of their blocks
+    a: Vec<T>,
+    // sorted copies of the blocks of a
+    sorted: Vec<Vec<T>>,
+    lazy: Vec<T>,
+}
+
+impl<T> BlockArray<T>
+where
+    T: Ord + Copy + Default + Add<Output = T>,
+{
+    // the block size is about sqrt(n)
+    pub fn new(a: &[T]) -> Self {
+        let mut block_size = 1;
+        while block_size * block_size < a.len() {
+            block_size += 1;
+        }
+        Self::with_block_size(a, block_size)
+    }
+
+    pub fn with_block_size(a: &[T], block_size: usize) -> Self {
+        assert!(block_size > 0);
+        let sorted = a
+            .chunks(block_size)
+            .map(|block| {
+                let mut block = block.to_vec();
+                block.sort();
+                block
+            })
+            .collect::<Vec<_>>();
+        BlockArray {
+            block_size,
+            a: a.to_vec(),
+            lazy: vec![T::default(); sorted.len()],
+            sorted,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
+    fn block_range(&self, b: usize) -> (usize, usize) {
+        (
+            b * self.block_size,
+            ((b + 1) * self.block_size).min(self.a.len()),
+        )
+    }
+
+    fn rebuild(&mut self, b: usize) {
+        let (l, r) = self.block_range(b);
+        self.sorted[b].clear();
+        self.sorted[b].extend_from_slice(&self.a[l..r]);
+        self.sorted[b].sort();
+    }
+
+    // apply the lazy value of the block to its elements
+    fn push(&mut self, b: usize) {
+        let (l, r) = self.block_range(b);
+        let lazy = std::mem::take(&mut self.lazy[b]);
+        for x in &mut self.a[l..r] {
+            *x = *x + lazy;
+        }
+        for x in &mut self.sorted[b] {
+            *x = *x + lazy;
+        }
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        self.a[i] + self.lazy[i / self.block_size]
+    }
+
+    pub fn set(&mut self, i: usize, x: T) {
+        let b = i / self.block_size;
+        self.push(b);
+        self.a[i] = x;
+        self.rebuild(b);
+    }
+
+    // add x to a[l..r]
+    pub fn range_add(&mut self, l: usize, r: usize, x: T) {
+        assert!(l <= r && r <= self.a.len());
+        if l == r {
+            return;
+        }
+        let (lb, rb) = (l / self.block_size, (r - 1) / self.block_size);
+        for b in lb..=rb {
+            let (bl, br) = self.block_range(b);
+            if l <= bl && br <= r {
+                self.lazy[b] = self.lazy[b] + x;
+            } else {
+                for y in &mut self.a[bl.max(l)..br.min(r)] {
+                    *y = *y + x;
+                }
+                self.rebuild(b);
+            }
+        }
+    }
+
+    // the number of the elements of a[l..r] not less than x
+    pub fn range_count_ge(&self, l: usize, r: usize, x: T) -> usize {
+        assert!(l <= r && r <= self.a.len());
+        if l == r {
+            return 0;
+        }
+        let (lb, rb) = (l / self.block_size, (r - 1) / self.block_size);
+        let mut res = 0;
+        for b in lb..=rb {
+            let (bl, br) = self.block_range(b);
+            let lazy = self.lazy[b];
+            if l <= bl && br <= r {
+                res += br - bl - self.sorted[b].partition_point(|&y| y + lazy < x);
+            } else {
+                res += self.a[bl.max(l)..br.min(r)]
+                    .iter()
+                    .filter(|&&y| y + lazy >= x)
+                    .count();
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockArray;
+    use crate::xorshift::XorShift;
+
+    #[test]
+    fn it_works() {
+        let mut a = BlockArray::with_block_size(&[5, 1, 4, 2, 8, 3, 7], 3);
+        assert_eq!(a.range_count_ge(0, 7, 4), 4);
+        assert_eq!(a.range_count_ge(1, 5, 3), 2);
+        a.range_add(2, 7, 10);
+        assert_eq!(
+            (0..7).map(|i| a.get(i)).collect::<Vec<_>>(),
+            vec![5, 1, 14, 12, 18, 13, 17]
+        );
+        assert_eq!(a.range_count_ge(0, 7, 13), 4);
+        a.set(4, 0);
+        assert_eq!(a.get(4), 0);
+        assert_eq!(a.get(5), 13);
+        assert_eq!(a.range_count_ge(3, 6, 1), 2);
+        assert_eq!(a.range_count_ge(3, 3, 1), 0);
+    }
+
+    #[test]
+    fn random_test() {
+        let mut rng = XorShift::default();
+
+        let n = 50;
+        let mut naive = (0..n)
+            .map(|_| rng.rand(100) as i64 - 50)
+            .collect::<Vec<_>>();
+        let mut a = BlockArray::new(&naive);
+        for _ in 0..3000 {
+            let l = rng.rand(n as u64 + 1) as usize;
+            let r = l + rng.rand((n - l) as u64 + 1) as usize;
+            let x = rng.rand(100) as i64 - 50;
+            match rng.rand(3) {
+                0 => {
+                    a.range_add(l, r, x);
+                    for y in &mut naive[l..r] {
+                        *y += x;
+                    }
+                }
+                1 => {
+                    if l < n {
+                        a.set(l, x);
+                        naive[l] = x;
+                    }
+                }
+                _ => {
+                    let expected = naive[l..r].iter().filter(|&&y| y >= x).count();
+                    assert_eq!(a.range_count_ge(l, r, x), expected);
+                }
+            }
+        }
+        for (i, &x) in naive.iter().enumerate() {
+            assert_eq!(a.get(i), x);
+        }
+    }
+}
@@ -1,7 +1,5 @@
-use std::{
-    collections::{BTreeSet, HashSet},
-    fmt, mem,
-};
+use crate::algebraic_structures::monoid::Monoid;
+use std::{collections::BTreeSet, fmt, mem};
 
 #[derive(Clone)]
 pub struct UnionFind {
@@ -31,7 +29,7 @@ impl UnionFind {
         self.number_of_nodes
     }
 
-    pub fn is_root(&mut self, x: usize) -> bool {
+    pub fn is_root(&self, x: usize) -> bool {
         self.parent[x] == x
     }
 
@@ -49,21 +47,25 @@ impl UnionFind {
         self.find(x) == self.find(y)
     }
 
-    pub fn union(&mut self, x: usize, y: usize) {
+    // return the root which survived if x and y were in different components, otherwise None
+    pub fn union(&mut self, x: usize, y: usize) -> Option<usize> {
         let (mut root_x, mut root_y) = (self.find(x), self.find(y));
 
-        if root_x != root_y {
-            if self.rank[root_x] < self.rank[root_y] {
-                mem::swap(&mut root_x, &mut root_y);
-            }
-
-            self.parent[root_y] = root_x;
-            self.size[root_x] += self.size[root_y];
-            if self.rank[root_x] == self.rank[root_y] {
-                self.rank[root_x] += 1;
-            }
-            self.number_of_connected_components -= 1;
+        if root_x == root_y {
+            return None;
         }
+
+        if self.rank[root_x] < self.rank[root_y] {
+            mem::swap(&mut root_x, &mut root_y);
+        }
+
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        if self.rank[root_x] == self.rank[root_y] {
+            self.rank[root_x] += 1;
+        }
+        self.number_of_connected_components -= 1;
+        Some(root_x)
     }
 
     pub fn size(&mut self, x: usize) -> usize {
@@ -75,8 +77,103 @@ impl UnionFind {
         }
     }
 
-    pub fn get_value(&mut self) -> HashSet<usize> {
-        HashSet::new()
+    // leader[x] = find(x)
+    pub fn leaders(&mut self) -> Vec<usize> {
+        (0..self.number_of_nodes).map(|x| self.find(x)).collect()
+    }
+
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.number_of_nodes)
+            .filter(|&x| self.is_root(x))
+            .collect()
+    }
+
+    // the members of each component in increasing order, the components ordered by their minimum
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let leaders = self.leaders();
+        let mut index = vec![None; self.number_of_nodes];
+        let mut res: Vec<Vec<usize>> = vec![];
+        for (x, &leader) in leaders.iter().enumerate() {
+            let i = *index[leader].get_or_insert_with(|| {
+                res.push(vec![]);
+                res.len() - 1
+            });
+            res[i].push(x);
+        }
+        res
+    }
+}
+
+// UnionFind which also keeps the fold of the values of each component
+pub struct MonoidUnionFind<M>
+where
+    M: Monoid,
+{
+    union_find: UnionFind,
+    value: Vec<M::S>,
+}
+
+impl<M> MonoidUnionFind<M>
+where
+    M: Monoid,
+    M::S: Clone + Copy,
+{
+    pub fn new(value: Vec<M::S>) -> Self {
+        MonoidUnionFind {
+            union_find: UnionFind::new(value.len()),
+            value,
+        }
+    }
+
+    pub fn get_number_of_connected_components(&self) -> usize {
+        self.union_find.get_number_of_connected_components()
+    }
+
+    pub fn get_number_of_nodes(&self) -> usize {
+        self.union_find.get_number_of_nodes()
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        self.union_find.find(x)
+    }
+
+    pub fn is_same(&mut self, x: usize, y: usize) -> bool {
+        self.union_find.is_same(x, y)
+    }
+
+    pub fn union(&mut self, x: usize, y: usize) -> Option<usize> {
+        let (root_x, root_y) = (self.find(x), self.find(y));
+        let root = self.union_find.union(x, y)?;
+        self.value[root] = M::op(self.value[root_x], self.value[root_y]);
+        Some(root)
+    }
+
+    pub fn size(&mut self, x: usize) -> usize {
+        self.union_find.size(x)
+    }
+
+    // fold of the values in the component of x
+    pub fn value(&mut self, x: usize) -> M::S {
+        let root = self.find(x);
+        self.value[root]
+    }
+
+    // multiply the fold of the component of x by a from the right
+    pub fn add_value(&mut self, x: usize, a: M::S) {
+        let root = self.find(x);
+        self.value[root] = M::op(self.value[root], a);
+    }
+
+    pub fn leaders(&mut self) -> Vec<usize> {
+        self.union_find.leaders()
+    }
+
+    pub fn roots(&self) -> Vec<usize> {
+        self.union_find.roots()
+    }
+
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        self.union_find.groups()
     }
 }
 
@@ -101,7 +198,8 @@ impl fmt::Display for UnionFind {
 
 #[cfg(test)]
 mod tests {
-    use super::UnionFind;
+    use super::{MonoidUnionFind, UnionFind};
+    use crate::algebraic_structures::monoid::{AddMonoid, MinMonoid};
 
     #[test]
     fn it_works() {
@@ -138,4 +236,49 @@ mod tests {
         assert_eq!(union_find.size(4), 2);
         assert_eq!(union_find.size(5), 2);
     }
+
+    #[test]
+    fn groups_test() {
+        let mut union_find = UnionFind::new(6);
+        assert_eq!(union_find.union(4, 1), Some(union_find.find(1)));
+        assert!(union_find.union(2, 5).is_some());
+        assert!(union_find.union(1, 5).is_some());
+        assert_eq!(union_find.union(4, 2), None);
+
+        assert_eq!(
+            union_find.groups(),
+            vec![vec![0], vec![1, 2, 4, 5], vec![3]]
+        );
+        let leaders = union_find.leaders();
+        assert_eq!(leaders[1], leaders[2]);
+        assert_eq!(leaders[1], leaders[4]);
+        assert_eq!(leaders[1], leaders[5]);
+        assert_eq!(leaders[0], 0);
+        assert_eq!(leaders[3], 3);
+        let mut roots = vec![0, leaders[1], 3];
+        roots.sort();
+        assert_eq!(union_find.roots(), roots);
+    }
+
+    #[test]
+    fn monoid_test() {
+        let mut union_find = MonoidUnionFind::<MinMonoid>::new(vec![5, 3, 8, 1, 9]);
+        union_find.union(0, 2);
+        assert_eq!(union_find.value(2), 5);
+        union_find.union(2, 1);
+        assert_eq!(union_find.value(0), 3);
+        assert_eq!(union_find.value(3), 1);
+        union_find.add_value(4, 0);
+        assert_eq!(union_find.value(4), 0);
+        assert_eq!(union_find.union(0, 1), None);
+        assert_eq!(union_find.groups(), vec![vec![0, 1, 2], vec![3], vec![4]]);
+
+        let mut union_find = MonoidUnionFind::<AddMonoid>::new(vec![5, 3, 8, 1, 9]);
+        union_find.union(0, 4);
+        union_find.union(3, 1);
+        union_find.union(1, 4);
+        assert_eq!(union_find.value(3), 18);
+        assert_eq!(union_find.value(2), 8);
+        assert_eq!(union_find.size(3), 4);
+    }
 }
@@ -0,0 +1,242 @@
+use crate::{
+    algorithms::{binary_search::BinarySearch, compress::Compress},
+    data_structures::bit_vector::BitVector,
+};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// Wavelet matrix over the compressed values of a
+// every query is O(log σ), where σ is the number of distinct values
+pub struct WaveletMatrix {
+    len: usize,
+    // the number of bits of the compressed values
+    height: usize,
+    // levels[d] holds the (height - 1 - d)-th bit
+    levels: Vec<BitVector>,
+    // the number of zeros in levels[d]
+    zeros: Vec<usize>,
+    scale: Vec<isize>,
+}
+
+impl WaveletMatrix {
+    pub fn new(a: &[isize]) -> WaveletMatrix {
+        let Compress {
+            mut coordinates,
+            scale,
+        } = Compress::new(&[a]);
+        let height = (usize::BITS - scale.len().saturating_sub(1).leading_zeros()) as usize;
+
+        let mut cur = coordinates.pop().unwrap();
+        let mut levels = Vec::with_capacity(height);
+        let mut zeros = Vec::with_capacity(height);
+        for d in 0..height {
+            let bit = height - 1 - d;
+            let bits = cur.iter().map(|&x| x >> bit & 1 == 1).collect::<Vec<_>>();
+            let bv = BitVector::new(&bits);
+            zeros.push(bv.count_zeros());
+            levels.push(bv);
+            let (mut next, ones): (Vec<usize>, Vec<usize>) =
+                cur.iter().partition(|&&x| x >> bit & 1 == 0);
+            next.extend(ones);
+            cur = next;
+        }
+
+        WaveletMatrix {
+            len: a.len(),
+            height,
+            levels,
+            zeros,
+            scale,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // the range [l, r) on the next level, following the bit b
+    fn next_range(&self, d: usize, l: usize, r: usize, b: bool) -> (usize, usize) {
+        let (l0, r0) = (self.levels[d].rank0(l), self.levels[d].rank0(r));
+        if b {
+            (self.zeros[d] + l - l0, self.zeros[d] + r - r0)
+        } else {
+            (l0, r0)
+        }
+    }
+
+    pub fn access(&self, mut i: usize) -> isize {
+        assert!(i < self.len);
+        let mut res = 0;
+        for d in 0..self.height {
+            let b = self.levels[d].get(i);
+            res = res << 1 | b as usize;
+            i = self.next_range(d, i, i + 1, b).0;
+        }
+        self.scale[res]
+    }
+
+    // the number of compressed values less than upper in a[l..r]
+    fn rank_less(&self, mut l: usize, mut r: usize, upper: usize) -> usize {
+        if upper >= 1 << self.height {
+            return r - l;
+        }
+        let mut res = 0;
+        for d in 0..self.height {
+            let b = upper >> (self.height - 1 - d) & 1 == 1;
+            if b {
+                res += self.levels[d].rank0(r) - self.levels[d].rank0(l);
+            }
+            (l, r) = self.next_range(d, l, r, b);
+        }
+        res
+    }
+
+    fn kth_smallest_compressed(&self, mut l: usize, mut r: usize, mut k: usize) -> usize {
+        let mut res = 0;
+        for d in 0..self.height {
+            let count0 = self.levels[d].rank0(r) - self.levels[d].rank0(l);
+            let b = k >= count0;
+            if b {
+                k -= count0;
+            }
+            res = res << 1 | b as usize;
+            (l, r) = self.next_range(d, l, r, b);
+        }
+        res
+    }
+
+    // the k-th smallest value in a[l..r] (0-based)
+    pub fn kth_smallest(&self, l: usize, r: usize, k: usize) -> Option<isize> {
+        assert!(l <= r && r <= self.len);
+        if k < r - l {
+            Some(self.scale[self.kth_smallest_compressed(l, r, k)])
+        } else {
+            None
+        }
+    }
+
+    // the k-th largest value in a[l..r] (0-based)
+    pub fn kth_largest(&self, l: usize, r: usize, k: usize) -> Option<isize> {
+        assert!(l <= r && r <= self.len);
+        if k < r - l {
+            self.kth_smallest(l, r, r - l - 1 - k)
+        } else {
+            None
+        }
+    }
+
+    // the number of x in a[l..r] such that lower <= x < upper
+    pub fn range_freq(&self, l: usize, r: usize, lower: isize, upper: isize) -> usize {
+        assert!(l <= r && r <= self.len);
+        if lower >= upper {
+            return 0;
+        }
+        let lower = self.scale.lower_bound(lower);
+        let upper = self.scale.lower_bound(upper);
+        self.rank_less(l, r, upper) - self.rank_less(l, r, lower)
+    }
+
+    // the maximum x in a[l..r] such that x < upper
+    pub fn prev_value(&self, l: usize, r: usize, upper: isize) -> Option<isize> {
+        assert!(l <= r && r <= self.len);
+        let count = self.rank_less(l, r, self.scale.lower_bound(upper));
+        if count == 0 {
+            None
+        } else {
+            Some(self.scale[self.kth_smallest_compressed(l, r, count - 1)])
+        }
+    }
+
+    // the minimum x in a[l..r] such that lower <= x
+    pub fn next_value(&self, l: usize, r: usize, lower: isize) -> Option<isize> {
+        assert!(l <= r && r <= self.len);
+        let count = self.rank_less(l, r, self.scale.lower_bound(lower));
+        if count == r - l {
+            None
+        } else {
+            Some(self.scale[self.kth_smallest_compressed(l, r, count)])
+        }
+    }
+
+    // the k most frequent values in a[l..r] with their frequencies,
+    // in decreasing order of frequency and then increasing order of value
+    pub fn top_k(&self, l: usize, r: usize, k: usize) -> Vec<(isize, usize)> {
+        assert!(l <= r && r <= self.len);
+        let mut res = vec![];
+        // (frequency, value prefix aligned to height bits, depth, l, r)
+        let mut que = BinaryHeap::new();
+        if l < r {
+            que.push((r - l, Reverse(0), 0, l, r));
+        }
+        while let Some((count, Reverse(value), d, l, r)) = que.pop() {
+            if res.len() == k {
+                break;
+            }
+            if d == self.height {
+                res.push((self.scale[value], count));
+                continue;
+            }
+            for b in [false, true] {
+                let (nl, nr) = self.next_range(d, l, r, b);
+                if nl < nr {
+                    let value = value | (b as usize) << (self.height - 1 - d);
+                    que.push((nr - nl, Reverse(value), d + 1, nl, nr));
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WaveletMatrix;
+
+    #[test]
+    fn it_works() {
+        let a = vec![5, -3, 8, 5, 0, 1_000_000_000, -3, 5, 2, 0];
+        let wm = WaveletMatrix::new(&a);
+        for (i, &x) in a.iter().enumerate() {
+            assert_eq!(wm.access(i), x);
+        }
+        for l in 0..=a.len() {
+            for r in l..=a.len() {
+                let mut b = a[l..r].to_vec();
+                b.sort();
+                for k in 0..=b.len() {
+                    assert_eq!(wm.kth_smallest(l, r, k), b.get(k).copied());
+                    assert_eq!(wm.kth_largest(l, r, k), b.iter().rev().nth(k).copied());
+                }
+                for lower in [-5, -3, 0, 1, 5, 6, 1_000_000_000] {
+                    for upper in [-3, 0, 3, 5, 8, 2_000_000_000] {
+                        assert_eq!(
+                            wm.range_freq(l, r, lower, upper),
+                            b.iter().filter(|&&x| lower <= x && x < upper).count()
+                        );
+                    }
+                    assert_eq!(
+                        wm.prev_value(l, r, lower),
+                        b.iter().filter(|&&x| x < lower).max().copied()
+                    );
+                    assert_eq!(
+                        wm.next_value(l, r, lower),
+                        b.iter().filter(|&&x| lower <= x).min().copied()
+                    );
+                }
+            }
+        }
+
+        assert_eq!(wm.top_k(0, 10, 3), vec![(5, 3), (-3, 2), (0, 2)]);
+        assert_eq!(wm.top_k(4, 10, 2), vec![(0, 2), (-3, 1)]);
+        assert_eq!(wm.top_k(2, 3, 5), vec![(8, 1)]);
+        assert_eq!(wm.top_k(2, 2, 5), vec![]);
+
+        let wm = WaveletMatrix::new(&[7, 7, 7]);
+        assert_eq!(wm.kth_smallest(0, 3, 2), Some(7));
+        assert_eq!(wm.range_freq(0, 3, 7, 8), 3);
+        assert_eq!(wm.top_k(0, 3, 1), vec![(7, 3)]);
+    }
+}
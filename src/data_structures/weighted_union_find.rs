@@ -0,0 +1,150 @@
+use crate::algebraic_structures::group::Group;
+use std::mem;
+
+// weight[x] = pot(parent[x])^{-1} * pot(x)
+pub struct WeightedUnionFind<G>
+where
+    G: Group,
+{
+    number_of_nodes: usize,
+    number_of_connected_components: usize,
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    size: Vec<usize>,
+    weight: Vec<G::S>,
+}
+
+impl<G> WeightedUnionFind<G>
+where
+    G: Group,
+    G::S: Clone + Copy + Eq,
+{
+    pub fn new(number_of_nodes: usize) -> Self {
+        WeightedUnionFind {
+            number_of_nodes,
+            number_of_connected_components: number_of_nodes,
+            parent: (0..number_of_nodes).collect::<Vec<usize>>(),
+            rank: vec![0; number_of_nodes],
+            size: vec![1; number_of_nodes],
+            weight: vec![G::id(); number_of_nodes],
+        }
+    }
+
+    pub fn get_number_of_connected_components(&self) -> usize {
+        self.number_of_connected_components
+    }
+
+    pub fn get_number_of_nodes(&self) -> usize {
+        self.number_of_nodes
+    }
+
+    pub fn is_root(&self, x: usize) -> bool {
+        self.parent[x] == x
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        if self.is_root(x) {
+            x
+        } else {
+            let p = self.parent[x];
+            let root = self.find(p);
+            self.weight[x] = G::op(self.weight[p], self.weight[x]);
+            self.parent[x] = root;
+            root
+        }
+    }
+
+    // return pot(root)^{-1} * pot(x)
+    pub fn potential(&mut self, x: usize) -> G::S {
+        self.find(x);
+        self.weight[x]
+    }
+
+    pub fn is_same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    // return pot(x)^{-1} * pot(y) if x and y are connected
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<G::S> {
+        if self.is_same(x, y) {
+            Some(G::op(G::inverse(self.weight[x]), self.weight[y]))
+        } else {
+            None
+        }
+    }
+
+    // add the constraint pot(y) = pot(x) * w
+    // return false (and leave the structure unchanged) if it contradicts the constraints so far
+    pub fn union(&mut self, x: usize, y: usize, w: G::S) -> bool {
+        let (mut root_x, mut root_y) = (self.find(x), self.find(y));
+        let (px, py) = (self.weight[x], self.weight[y]);
+
+        if root_x == root_y {
+            return G::op(G::inverse(px), py) == w;
+        }
+
+        // pot(root_x)^{-1} * pot(root_y)
+        let mut w = G::op(G::op(px, w), G::inverse(py));
+        if self.rank[root_x] < self.rank[root_y] {
+            mem::swap(&mut root_x, &mut root_y);
+            w = G::inverse(w);
+        }
+
+        self.parent[root_y] = root_x;
+        self.weight[root_y] = w;
+        self.size[root_x] += self.size[root_y];
+        if self.rank[root_x] == self.rank[root_y] {
+            self.rank[root_x] += 1;
+        }
+        self.number_of_connected_components -= 1;
+        true
+    }
+
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WeightedUnionFind;
+    use crate::algebraic_structures::group::{AddGroup, XorGroup};
+
+    #[test]
+    fn add_test() {
+        let mut uf = WeightedUnionFind::<AddGroup>::new(6);
+        assert!(uf.union(0, 1, 3));
+        assert!(uf.union(1, 2, -5));
+        assert!(uf.union(4, 3, 7));
+        assert_eq!(uf.get_number_of_connected_components(), 3);
+
+        assert_eq!(uf.diff(0, 2), Some(-2));
+        assert_eq!(uf.diff(2, 0), Some(2));
+        assert_eq!(uf.diff(4, 3), Some(7));
+        assert_eq!(uf.diff(0, 3), None);
+
+        assert!(uf.union(2, 3, 1));
+        assert_eq!(uf.diff(0, 4), Some(-8));
+        assert_eq!(uf.diff(4, 1), Some(11));
+        assert_eq!(uf.size(4), 5);
+
+        assert!(uf.union(0, 4, -8));
+        assert!(!uf.union(0, 4, 0));
+        assert_eq!(uf.diff(0, 4), Some(-8));
+        assert_eq!(uf.get_number_of_connected_components(), 2);
+    }
+
+    #[test]
+    fn xor_test() {
+        let mut uf = WeightedUnionFind::<XorGroup>::new(4);
+        assert!(uf.union(0, 1, 0b101));
+        assert!(uf.union(2, 1, 0b011));
+        assert_eq!(uf.diff(0, 2), Some(0b110));
+        assert_eq!(uf.diff(2, 0), Some(0b110));
+        assert!(!uf.union(0, 2, 0b111));
+        assert!(uf.union(3, 0, 0b001));
+        assert_eq!(uf.diff(3, 2), Some(0b111));
+        assert_eq!(uf.size(3), 4);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod scanner;
+pub mod writer;
@@ -0,0 +1,193 @@
+use std::{io::BufRead, str::FromStr};
+
+// whitespace separated token reader over any BufRead, such as stdin or an in-memory cursor
+pub struct Scanner<R> {
+    reader: R,
+    // the current line and the position in it
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R> Scanner<R>
+where
+    R: BufRead,
+{
+    pub fn new(reader: R) -> Scanner<R> {
+        Scanner {
+            reader,
+            buf: vec![],
+            pos: 0,
+        }
+    }
+
+    // the next token, None at the end of the input
+    pub fn try_token(&mut self) -> Option<&str> {
+        loop {
+            while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos < self.buf.len() {
+                let start = self.pos;
+                while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+                    self.pos += 1;
+                }
+                return Some(std::str::from_utf8(&self.buf[start..self.pos]).unwrap());
+            }
+            self.buf.clear();
+            self.pos = 0;
+            if self.reader.read_until(b'\n', &mut self.buf).unwrap() == 0 {
+                return None;
+            }
+        }
+    }
+
+    pub fn token(&mut self) -> &str {
+        self.try_token().expect("unexpected end of input")
+    }
+
+    pub fn read<T>(&mut self) -> T::Output
+    where
+        T: Readable,
+    {
+        T::read(self)
+    }
+
+    pub fn read_vec<T>(&mut self, n: usize) -> Vec<T::Output>
+    where
+        T: Readable,
+    {
+        (0..n).map(|_| T::read(self)).collect()
+    }
+}
+
+// the types which can be read from Scanner, where Output is the type of the read value
+pub trait Readable {
+    type Output;
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self::Output;
+}
+
+macro_rules! impl_readable {
+    ($($t:ty),*) => {
+        $(
+            impl Readable for $t {
+                type Output = $t;
+
+                fn read<R: BufRead>(scanner: &mut Scanner<R>) -> $t {
+                    let token = scanner.token();
+                    <$t as FromStr>::from_str(token)
+                        .unwrap_or_else(|_| panic!("cannot parse {:?}", token))
+                }
+            }
+        )*
+    };
+}
+
+impl_readable!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char, bool, String
+);
+
+// usize converted from 1-indexed to 0-indexed
+pub enum Usize1 {}
+
+impl Readable for Usize1 {
+    type Output = usize;
+
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> usize {
+        usize::read(scanner)
+            .checked_sub(1)
+            .expect("Usize1 must be at least 1")
+    }
+}
+
+// isize converted from 1-indexed to 0-indexed
+pub enum Isize1 {}
+
+impl Readable for Isize1 {
+    type Output = isize;
+
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> isize {
+        isize::read(scanner) - 1
+    }
+}
+
+// a token as Vec<char>
+pub enum Chars {}
+
+impl Readable for Chars {
+    type Output = Vec<char>;
+
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Vec<char> {
+        scanner.token().chars().collect()
+    }
+}
+
+// a token as Vec<u8>
+pub enum Bytes {}
+
+impl Readable for Bytes {
+    type Output = Vec<u8>;
+
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Vec<u8> {
+        scanner.token().bytes().collect()
+    }
+}
+
+macro_rules! impl_readable_tuple {
+    ($($t:ident),*) => {
+        impl<$($t),*> Readable for ($($t,)*)
+        where
+            $($t: Readable),*
+        {
+            type Output = ($($t::Output,)*);
+
+            fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self::Output {
+                ($($t::read(scanner),)*)
+            }
+        }
+    };
+}
+
+impl_readable_tuple!(A);
+impl_readable_tuple!(A, B);
+impl_readable_tuple!(A, B, C);
+impl_readable_tuple!(A, B, C, D);
+impl_readable_tuple!(A, B, C, D, E);
+
+impl<T, const N: usize> Readable for [T; N]
+where
+    T: Readable,
+{
+    type Output = [T::Output; N];
+
+    fn read<R: BufRead>(scanner: &mut Scanner<R>) -> Self::Output {
+        std::array::from_fn(|_| T::read(scanner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bytes, Chars, Isize1, Scanner, Usize1};
+    use std::io::Cursor;
+
+    #[test]
+    fn it_works() {
+        let input = "3 -5\n  abc\n\n1 2.5 x\r\n7 8 9\n10 20\ntrue 0";
+        let mut sc = Scanner::new(Cursor::new(input));
+        assert_eq!(sc.read::<usize>(), 3);
+        assert_eq!(sc.read::<i64>(), -5);
+        assert_eq!(sc.read::<Chars>(), vec!['a', 'b', 'c']);
+        assert_eq!(sc.read::<(Usize1, f64, char)>(), (0, 2.5, 'x'));
+        assert_eq!(sc.read::<[u32; 3]>(), [7, 8, 9]);
+        assert_eq!(sc.read_vec::<Isize1>(2), vec![9, 19]);
+        assert!(sc.read::<bool>());
+        assert_eq!(sc.read::<Bytes>(), b"0".to_vec());
+        assert_eq!(sc.try_token(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn end_of_input() {
+        let mut sc = Scanner::new(Cursor::new("1"));
+        sc.read::<(i32, i32)>();
+    }
+}
@@ -0,0 +1,79 @@
+use std::{
+    fmt::Display,
+    io::{self, BufWriter, Write},
+};
+
+// buffered writer, which is flushed on drop
+// Use outln! / out! (or writeln! / write!) for formatted output.
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: BufWriter<W>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    pub fn new(inner: W) -> Writer<W> {
+        Writer {
+            inner: BufWriter::new(inner),
+        }
+    }
+
+    // write the elements separated by sep, followed by a newline
+    pub fn write_joined<I>(&mut self, iter: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for (i, x) in iter.into_iter().enumerate() {
+            if i > 0 {
+                self.inner.write_all(sep.as_bytes()).unwrap();
+            }
+            write!(self.inner, "{}", x).unwrap();
+        }
+        self.inner.write_all(b"\n").unwrap();
+    }
+
+    // flush and return the underlying writer
+    pub fn into_inner(self) -> W {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(|_| panic!("failed to flush"))
+    }
+}
+
+impl<W> Write for Writer<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Writer;
+    use std::io::Write;
+
+    #[test]
+    fn it_works() {
+        let mut w = Writer::new(vec![]);
+        write!(w, "{} ", 1).unwrap();
+        writeln!(w, "a").unwrap();
+        w.write_joined([3, 1, 4], " ");
+        w.write_joined(Vec::<i32>::new(), " ");
+        w.write_joined(['x', 'y'], "");
+        assert_eq!(
+            String::from_utf8(w.into_inner()).unwrap(),
+            "1 a\n3 1 4\n\nxy\n"
+        );
+    }
+}
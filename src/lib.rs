@@ -1,7 +1,10 @@
 pub mod algebraic_structures;
 pub mod algorithms;
 pub mod data_structures;
+pub mod io;
 pub mod macros;
+#[cfg(test)]
+mod xorshift;
 
 #[cfg(test)]
 mod tests {
@@ -1 +1,2 @@
 pub mod ch_min_max;
+pub mod io;
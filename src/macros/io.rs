@@ -0,0 +1,123 @@
+// input! {
+//     sc,
+//     n: usize,
+//     mut a: [i64; n],
+//     edges: [(Usize1, Usize1); n - 1],
+//     s: Chars,
+// }
+// where sc is a Scanner
+#[macro_export]
+macro_rules! input {
+    ($sc: expr, $($rest: tt)*) => {
+        $crate::__input_inner!($sc, $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __input_inner {
+    ($sc: expr $(,)?) => {};
+    ($sc: expr, mut $var: ident : $t: tt $($rest: tt)*) => {
+        let mut $var = $crate::__input_read_value!($sc, $t);
+        $crate::__input_inner!($sc $($rest)*);
+    };
+    ($sc: expr, $var: ident : $t: tt $($rest: tt)*) => {
+        let $var = $crate::__input_read_value!($sc, $t);
+        $crate::__input_inner!($sc $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __input_read_value {
+    ($sc: expr, ($($t: tt),*)) => {
+        ($($crate::__input_read_value!($sc, $t),)*)
+    };
+    ($sc: expr, [$t: tt; $len: expr]) => {
+        (0..$len)
+            .map(|_| $crate::__input_read_value!($sc, $t))
+            .collect::<Vec<_>>()
+    };
+    ($sc: expr, $t: ty) => {
+        $sc.read::<$t>()
+    };
+}
+
+// println! to a writer, e.g. outln!(w, "{} {}", a, b)
+// std::io::Write is imported inside the expansion, so the caller does not need it in scope.
+#[macro_export]
+macro_rules! outln {
+    ($w: expr) => {{
+        use ::std::io::Write as _;
+        ::std::writeln!($w).unwrap()
+    }};
+    ($w: expr, $($arg: tt)*) => {{
+        use ::std::io::Write as _;
+        ::std::writeln!($w, $($arg)*).unwrap()
+    }};
+}
+
+// print! to a writer
+#[macro_export]
+macro_rules! out {
+    ($w: expr, $($arg: tt)*) => {{
+        use ::std::io::Write as _;
+        ::std::write!($w, $($arg)*).unwrap()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{
+        scanner::{Chars, Scanner, Usize1},
+        writer::Writer,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn input_test() {
+        let input = "3 2\n5 -1 4\n1 2\n2 3\nabc\n1 2 3 4\n";
+        let mut sc = Scanner::new(Cursor::new(input));
+        input! {
+            sc,
+            n: usize,
+            m: usize,
+            mut a: [i64; n],
+            edges: [(Usize1, Usize1); m],
+            s: Chars,
+            grid: [[u8; 2]; 2],
+        }
+        a.sort();
+        assert_eq!(a, vec![-1, 4, 5]);
+        assert_eq!(edges, vec![(0, 1), (1, 2)]);
+        assert_eq!(s, vec!['a', 'b', 'c']);
+        assert_eq!(grid, vec![vec![1, 2], vec![3, 4]]);
+
+        let mut sc = Scanner::new(Cursor::new("7"));
+        input!(sc, x: (i32,));
+        assert_eq!(x, (7,));
+    }
+
+    #[test]
+    fn output_test() {
+        let mut w = Writer::new(vec![]);
+        outln!(w, "{} {}", 1, 2);
+        out!(w, "{}", 'a');
+        out!(w, "b");
+        outln!(w);
+        outln!(w, "end");
+        write_to(&mut w, 3);
+        let mut v = vec![];
+        outln!(v, "{}", 4);
+        out!(&mut v, "x");
+        assert_eq!(
+            String::from_utf8(w.into_inner()).unwrap(),
+            "1 2\nab\nend\n3\n"
+        );
+        assert_eq!(v, b"4\nx");
+    }
+
+    fn write_to(w: &mut Writer<Vec<u8>>, x: i32) {
+        outln!(w, "{}", x);
+    }
+}
@@ -1,3 +1,1029 @@
+// Bundler for submitting a solution to judges which accept only a single file.
+//
+// cargo run --release -- <solution.rs> [-o <output.rs>] [-l <library>]
+//
+// <library> is the directory of this crate, which defaults to the one the bundler was built from.
+// The modules of the library used by the solution, through the paths algorithms_and_data_structures_rust::...
+// or the exported macros such as chmin!, are resolved transitively and appended to the solution
+// as nested modules of algorithms_and_data_structures_rust.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+const CRATE_NAME: &str = "algorithms_and_data_structures_rust";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TokenKind {
+    Ident,
+    // ::
+    PathSep,
+    // any other single character, or a literal
+    Punct,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    // byte offsets of [start, end) in the source
+    start: usize,
+    end: usize,
+}
+
+impl Token<'_> {
+    fn is(&self, kind: TokenKind, text: &str) -> bool {
+        self.kind == kind && self.text == text
+    }
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_'
+}
+
+fn is_ident_continue(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+// the end of the string literal starting at i, where s[i] is the opening quote
+fn skip_string(s: &[u8], mut i: usize) -> usize {
+    i += 1;
+    while i < s.len() && s[i] != b'"' {
+        if s[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i + 1
+}
+
+// whether a raw string literal such as r"..." or r##"..."## starts at i
+fn is_raw_string(s: &[u8], i: usize) -> bool {
+    s.get(i) == Some(&b'r') && s[i + 1..].iter().find(|&&c| c != b'#') == Some(&b'"')
+}
+
+// the end of the raw string literal starting at i, where s[i] is 'r'
+fn skip_raw_string(s: &[u8], mut i: usize) -> usize {
+    i += 1;
+    let mut hashes = 0;
+    while i < s.len() && s[i] == b'#' {
+        hashes += 1;
+        i += 1;
+    }
+    i += 1;
+    loop {
+        if i >= s.len() {
+            return s.len();
+        }
+        if s[i] == b'"'
+            && s[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&c| c == b'#')
+                .count()
+                == hashes
+        {
+            return i + 1 + hashes;
+        }
+        i += 1;
+    }
+}
+
+// split the source into tokens, skipping whitespace, comments and the contents of literals
+fn tokenize(src: &str) -> Vec<Token<'_>> {
+    let s = src.as_bytes();
+    let mut res = vec![];
+    let mut i = 0;
+    while i < s.len() {
+        let c = s[i];
+        let start = i;
+        let kind;
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if s[i..].starts_with(b"//") {
+            while i < s.len() && s[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        } else if s[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            while i < s.len() {
+                if s[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if s[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            continue;
+        } else if is_raw_string(s, i) {
+            i = skip_raw_string(s, i);
+            kind = TokenKind::Punct;
+        } else if c == b'b' && is_raw_string(s, i + 1) {
+            i = skip_raw_string(s, i + 1);
+            kind = TokenKind::Punct;
+        } else if s[i..].starts_with(b"b\"") {
+            i = skip_string(s, i + 1);
+            kind = TokenKind::Punct;
+        } else if c == b'"' {
+            i = skip_string(s, i);
+            kind = TokenKind::Punct;
+        } else if c == b'\'' {
+            // a char literal or a lifetime
+            let next = src[i + 1..].chars().next().map_or(0, |c| c.len_utf8());
+            if s.get(i + 1) == Some(&b'\\') {
+                i += 2;
+                while i < s.len() && s[i] != b'\'' {
+                    i += 1;
+                }
+                i += 1;
+            } else if s.get(i + 1 + next) == Some(&b'\'') {
+                i += 2 + next;
+            } else {
+                i += 1;
+            }
+            kind = TokenKind::Punct;
+        } else if is_ident_start(c)
+            || (c == b'$' && s.get(i + 1).is_some_and(|&c| is_ident_start(c)))
+        {
+            i += 1;
+            while i < s.len() && is_ident_continue(s[i]) {
+                i += 1;
+            }
+            kind = TokenKind::Ident;
+        } else if c.is_ascii_digit() {
+            while i < s.len() && (is_ident_continue(s[i]) || s[i] == b'.') {
+                // stop before a range such as 0..n
+                if s[i] == b'.' && s.get(i + 1) == Some(&b'.') {
+                    break;
+                }
+                i += 1;
+            }
+            kind = TokenKind::Punct;
+        } else if s[i..].starts_with(b"::") {
+            i += 2;
+            kind = TokenKind::PathSep;
+        } else {
+            i += src[i..].chars().next().unwrap().len_utf8();
+            kind = TokenKind::Punct;
+        }
+        res.push(Token {
+            kind,
+            text: &src[start..i.min(s.len())],
+            start,
+            end: i.min(s.len()),
+        });
+    }
+    res
+}
+
+// parse the use tree starting at tokens[i], which follows ::
+// return the paths relative to the prefix and the index after the tree
+fn parse_tree(tokens: &[Token], i: usize) -> (Vec<Vec<String>>, usize) {
+    match tokens.get(i) {
+        Some(t) if t.is(TokenKind::Punct, "{") => {
+            let mut res = vec![];
+            let mut i = i + 1;
+            while i < tokens.len() && !tokens[i].is(TokenKind::Punct, "}") {
+                let (paths, next) = parse_tree(tokens, i);
+                res.extend(paths);
+                i = next;
+                // skip such as `as alias` up to the next item
+                let mut depth = 0;
+                while i < tokens.len() {
+                    let t = tokens[i];
+                    if depth == 0 && (t.is(TokenKind::Punct, ",") || t.is(TokenKind::Punct, "}")) {
+                        break;
+                    }
+                    if t.is(TokenKind::Punct, "{") {
+                        depth += 1;
+                    } else if t.is(TokenKind::Punct, "}") {
+                        depth -= 1;
+                    }
+                    i += 1;
+                }
+                if i < tokens.len() && tokens[i].is(TokenKind::Punct, ",") {
+                    i += 1;
+                }
+            }
+            (res, i + 1)
+        }
+        Some(t) if t.kind == TokenKind::Ident => {
+            let segment = if t.text == "self" {
+                vec![]
+            } else {
+                vec![t.text.to_string()]
+            };
+            if tokens
+                .get(i + 1)
+                .is_some_and(|t| t.kind == TokenKind::PathSep)
+            {
+                let (paths, next) = parse_tree(tokens, i + 2);
+                let paths = paths
+                    .into_iter()
+                    .map(|path| segment.iter().cloned().chain(path).collect())
+                    .collect();
+                (paths, next)
+            } else {
+                (vec![segment], i + 1)
+            }
+        }
+        _ => (vec![vec![]], i + 1),
+    }
+}
+
+// the library source split into modules
+struct Library {
+    src_dir: PathBuf,
+    // macro name -> the module defining it
+    macros: BTreeMap<String, Vec<String>>,
+    // type name -> the modules defining it
+    types: BTreeMap<String, Vec<Vec<String>>>,
+    // inherent impls outside the modules of their types, such as impl Graph<usize> in algorithms::dijkstra
+    extensions: Vec<Extension>,
+}
+
+struct Extension {
+    module: Vec<String>,
+    ty: String,
+    methods: BTreeSet<String>,
+}
+
+impl Library {
+    fn new(src_dir: &Path) -> Library {
+        let mut res = Library {
+            src_dir: src_dir.to_path_buf(),
+            macros: BTreeMap::new(),
+            types: BTreeMap::new(),
+            extensions: vec![],
+        };
+        let mut stack = vec![(src_dir.to_path_buf(), vec![])];
+        while let Some((dir, module)) = stack.pop() {
+            let mut entries = fs::read_dir(&dir)
+                .unwrap_or_else(|e| panic!("cannot read {}: {}", dir.display(), e))
+                .map(|e| e.unwrap().path())
+                .collect::<Vec<_>>();
+            entries.sort();
+            for path in entries {
+                let name = path.file_stem().unwrap().to_string_lossy().to_string();
+                let mut module = module.clone();
+                if path.is_dir() {
+                    module.push(name);
+                    stack.push((path, module));
+                    continue;
+                }
+                if path.extension().is_none_or(|e| e != "rs") {
+                    continue;
+                }
+                if module.is_empty() && (name == "lib" || name == "main") {
+                    continue;
+                }
+                if name != "mod" {
+                    module.push(name);
+                }
+                let src = fs::read_to_string(&path).unwrap();
+                let tokens = tokenize(&src);
+                let mut exported = false;
+                for (i, t) in tokens.iter().enumerate() {
+                    if t.is(TokenKind::Ident, "macro_export") {
+                        exported = true;
+                    } else if t.is(TokenKind::Ident, "macro_rules") {
+                        if exported {
+                            res.macros
+                                .insert(tokens[i + 2].text.to_string(), module.clone());
+                        }
+                        exported = false;
+                    } else if (t.is(TokenKind::Ident, "struct")
+                        || t.is(TokenKind::Ident, "enum")
+                        || t.is(TokenKind::Ident, "type"))
+                        && tokens
+                            .get(i + 1)
+                            .is_some_and(|t| t.kind == TokenKind::Ident)
+                    {
+                        res.types
+                            .entry(tokens[i + 1].text.to_string())
+                            .or_default()
+                            .push(module.clone());
+                    } else if t.is(TokenKind::Ident, "impl") {
+                        if let Some((ty, methods)) = inherent_impl(&tokens, i) {
+                            res.extensions.push(Extension {
+                                module: module.clone(),
+                                ty,
+                                methods,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        let types = &res.types;
+        res.extensions
+            .retain(|e| types.get(&e.ty).is_some_and(|m| !m.contains(&e.module)));
+        res
+    }
+
+    fn module_file(&self, module: &[String]) -> Option<PathBuf> {
+        let mut path = self.src_dir.clone();
+        for segment in module {
+            path.push(segment);
+        }
+        let file = path.with_extension("rs");
+        if file.is_file() {
+            return Some(file);
+        }
+        let file = path.join("mod.rs");
+        if file.is_file() {
+            Some(file)
+        } else {
+            None
+        }
+    }
+
+    // the source of the module without the tests
+    fn module_source(&self, module: &[String]) -> String {
+        let src = fs::read_to_string(self.module_file(module).unwrap()).unwrap();
+        strip_tests(&src)
+    }
+
+    // the longest prefix of the path which is a module, or the module of the macro
+    fn resolve(&self, path: &[String]) -> Option<Vec<String>> {
+        let mut module = vec![];
+        for segment in path {
+            module.push(segment.clone());
+            if self.module_file(&module).is_none() {
+                module.pop();
+                break;
+            }
+        }
+        if !module.is_empty() {
+            return Some(module);
+        }
+        path.first().and_then(|name| self.macros.get(name).cloned())
+    }
+
+    // the modules used in src, where current is the module of src (None for the solution)
+    fn dependencies(&self, src: &str, current: Option<&[String]>) -> BTreeSet<Vec<String>> {
+        let tokens = tokenize(src);
+        let mut res = BTreeSet::new();
+        for (i, t) in tokens.iter().enumerate() {
+            if t.kind != TokenKind::Ident {
+                continue;
+            }
+            let next = tokens.get(i + 1);
+            if next.is_some_and(|n| n.is(TokenKind::Punct, "!"))
+                && !tokens
+                    .get(i + 2)
+                    .is_some_and(|n| n.is(TokenKind::Punct, "="))
+            {
+                if let Some(module) = self.macros.get(t.text) {
+                    res.insert(module.clone());
+                }
+            }
+            if !next.is_some_and(|n| n.kind == TokenKind::PathSep) {
+                continue;
+            }
+            // the previous token must not be a part of the same path
+            if i > 0 && tokens[i - 1].kind == TokenKind::PathSep {
+                continue;
+            }
+            let base = match (t.text, current) {
+                (CRATE_NAME, None) => vec![],
+                ("crate" | "$crate", Some(_)) => vec![],
+                ("self" | "super", Some(current)) => current.to_vec(),
+                _ => continue,
+            };
+            let (paths, _) = if matches!(t.text, "self" | "super") {
+                parse_tree(&tokens, i)
+            } else {
+                parse_tree(&tokens, i + 2)
+            };
+            for path in paths {
+                let mut absolute = base.clone();
+                let mut rest = path.as_slice();
+                while let Some((first, tail)) = rest.split_first() {
+                    if first != "super" {
+                        break;
+                    }
+                    absolute.pop();
+                    rest = tail;
+                }
+                absolute.extend(rest.iter().cloned());
+                if let Some(module) = self.resolve(&absolute) {
+                    res.insert(module);
+                }
+            }
+        }
+        res
+    }
+
+    // the modules used by the solution and their dependencies, with their ancestors,
+    // and the modules with the inherent impls whose methods are called on the included types
+    fn collect(&self, solution: &str) -> BTreeSet<Vec<String>> {
+        let mut res = BTreeSet::new();
+        let mut called = method_calls(solution);
+        let mut stack = self
+            .dependencies(solution, None)
+            .into_iter()
+            .collect::<Vec<_>>();
+        loop {
+            while let Some(module) = stack.pop() {
+                if !res.insert(module.clone()) {
+                    continue;
+                }
+                if module.len() > 1 {
+                    stack.push(module[..module.len() - 1].to_vec());
+                }
+                let src = self.module_source(&module);
+                called.extend(method_calls(&src));
+                stack.extend(self.dependencies(&src, Some(&module)));
+            }
+            stack.extend(
+                self.extensions
+                    .iter()
+                    .filter(|e| {
+                        !res.contains(&e.module)
+                            && self.types[&e.ty].iter().any(|m| res.contains(m))
+                            && !e.methods.is_disjoint(&called)
+                    })
+                    .map(|e| e.module.clone()),
+            );
+            if stack.is_empty() {
+                return res;
+            }
+        }
+    }
+
+    // prefix the paths from the crate root such as crate::algorithms with the library module,
+    // where the exported macros are re-exported from the library module,
+    // and remove the declarations of the submodules, which are inlined instead
+    fn rewrite(&self, src: &str) -> String {
+        let tokens = tokenize(src);
+        let mut edits = vec![];
+        for (i, t) in tokens.iter().enumerate() {
+            if (t.is(TokenKind::Ident, "crate") || t.is(TokenKind::Ident, "$crate"))
+                && tokens
+                    .get(i + 1)
+                    .is_some_and(|t| t.kind == TokenKind::PathSep)
+            {
+                edits.push((
+                    tokens[i + 1].end,
+                    tokens[i + 1].end,
+                    format!("{}::", CRATE_NAME),
+                ));
+            }
+            // mod name;
+            if t.is(TokenKind::Ident, "mod")
+                && tokens
+                    .get(i + 2)
+                    .is_some_and(|t| t.is(TokenKind::Punct, ";"))
+            {
+                let start = if i > 0 && tokens[i - 1].is(TokenKind::Ident, "pub") {
+                    tokens[i - 1].start
+                } else {
+                    t.start
+                };
+                edits.push((start, tokens[i + 2].end, String::new()));
+            }
+        }
+        apply_edits(src, edits)
+    }
+
+    // remove the exported macros from `use algorithms_and_data_structures_rust::...`,
+    // since they are defined at the crate root of the bundled file and conflict with the imports
+    fn remove_macro_imports(&self, src: &str) -> String {
+        let tokens = tokenize(src);
+        let texts = tokens.iter().map(|t| t.text).collect::<Vec<_>>();
+        let is_macro = |item: &[Token]| {
+            item.len() == 1
+                && item[0].kind == TokenKind::Ident
+                && self.macros.contains_key(item[0].text)
+        };
+        let mut edits = vec![];
+        for i in 0..tokens.len() {
+            if !texts[i..].starts_with(&["use", CRATE_NAME, "::"]) {
+                continue;
+            }
+            let Some(end) = (i..tokens.len()).find(|&j| texts[j] == ";") else {
+                break;
+            };
+            let start = if i > 0 && texts[i - 1] == "pub" {
+                tokens[i - 1].start
+            } else {
+                tokens[i].start
+            };
+            let tree = &tokens[i + 3..end];
+            if is_macro(tree) {
+                edits.push((start, tokens[end].end, String::new()));
+                continue;
+            }
+            if !(tree.first().is_some_and(|t| t.text == "{")
+                && tree.last().is_some_and(|t| t.text == "}"))
+            {
+                continue;
+            }
+            // the items of the group, split by the commas at the top level
+            let mut items = vec![];
+            let mut depth = 0;
+            let mut first = 1;
+            for (j, t) in tree.iter().enumerate().skip(1) {
+                match t.text {
+                    "{" => depth += 1,
+                    "}" if depth > 0 => depth -= 1,
+                    "," | "}" if depth == 0 => {
+                        if first < j {
+                            items.push(&tree[first..j]);
+                        }
+                        first = j + 1;
+                    }
+                    _ => {}
+                }
+            }
+            if !items.iter().any(|item| is_macro(item)) {
+                continue;
+            }
+            let kept = items
+                .iter()
+                .filter(|item| !is_macro(item))
+                .map(|item| &src[item[0].start..item.last().unwrap().end])
+                .collect::<Vec<_>>();
+            if kept.is_empty() {
+                edits.push((start, tokens[end].end, String::new()));
+            } else {
+                edits.push((
+                    tree[0].start,
+                    tree.last().unwrap().end,
+                    format!("{{{}}}", kept.join(", ")),
+                ));
+            }
+        }
+        apply_edits(src, edits)
+    }
+
+    fn bundle(&self, solution: &str) -> String {
+        let modules = self.collect(solution);
+        let solution = self.remove_macro_imports(&remove_extern_crate(solution));
+        let mut res = solution.trim_end().to_string();
+        if modules.is_empty() {
+            res.push('\n');
+            return res;
+        }
+        res.push_str("\n\n// The following is the bundled library.\n");
+        res.push_str("#[allow(dead_code, unused_imports, unused_macros, clippy::all)]\n");
+        res.push_str(&format!("pub mod {} {{\n", CRATE_NAME));
+        let macros = self
+            .macros
+            .iter()
+            .filter(|(_, module)| modules.contains(*module))
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        if !macros.is_empty() {
+            res.push_str(&format!("pub use crate::{{{}}};\n", macros.join(", ")));
+        }
+        let mut stack: Vec<&[String]> = vec![];
+        for module in &modules {
+            while !stack.is_empty() && !module.starts_with(stack.last().unwrap()) {
+                stack.pop();
+                res.push_str("}\n");
+            }
+            res.push_str(&format!("pub mod {} {{\n", module.last().unwrap()));
+            let src = self.rewrite(&self.module_source(module));
+            let src = src.trim();
+            if !src.is_empty() {
+                res.push_str(src);
+                res.push('\n');
+            }
+            stack.push(module);
+        }
+        for _ in 0..=stack.len() {
+            res.push_str("}\n");
+        }
+        res
+    }
+}
+
+// the type name and the method names of the impl block starting at tokens[i],
+// or None if it is a trait impl or not an impl block
+fn inherent_impl(tokens: &[Token], i: usize) -> Option<(String, BTreeSet<String>)> {
+    let mut j = i + 1;
+    let mut depth = 0;
+    let mut ty = None;
+    // the header up to the opening brace, where the type is the last identifier outside <>
+    while j < tokens.len() {
+        let t = tokens[j];
+        match t.text {
+            "<" => depth += 1,
+            ">" if !tokens[j - 1].is(TokenKind::Punct, "-") => depth -= 1,
+            "{" | "where" if depth == 0 => break,
+            "for" | ";" | "(" | ")" | "," | "=" if depth == 0 => return None,
+            _ if depth == 0 && t.kind == TokenKind::Ident => ty = Some(t.text),
+            _ => {}
+        }
+        j += 1;
+    }
+    while j < tokens.len() && !tokens[j].is(TokenKind::Punct, "{") {
+        j += 1;
+    }
+    let ty = ty?.to_string();
+    let mut methods = BTreeSet::new();
+    let mut depth = 0;
+    while j < tokens.len() {
+        match tokens[j].text {
+            "{" => depth += 1,
+            "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            "fn" if depth == 1 => {
+                if let Some(name) = tokens.get(j + 1) {
+                    methods.insert(name.text.to_string());
+                }
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    Some((ty, methods))
+}
+
+// the names which may be called as methods, such as f in x.f() or T::f()
+fn method_calls(src: &str) -> BTreeSet<String> {
+    let tokens = tokenize(src);
+    let mut res = BTreeSet::new();
+    for i in 1..tokens.len() {
+        if tokens[i].kind == TokenKind::Ident
+            && (tokens[i - 1].is(TokenKind::Punct, ".")
+                || (tokens[i - 1].kind == TokenKind::PathSep
+                    && tokens
+                        .get(i + 1)
+                        .is_some_and(|t| t.is(TokenKind::Punct, "("))))
+        {
+            res.insert(tokens[i].text.to_string());
+        }
+    }
+    res
+}
+
+// apply the replacements (start, end, text), which must not overlap
+fn apply_edits(src: &str, mut edits: Vec<(usize, usize, String)>) -> String {
+    edits.sort_by_key(|&(start, end, _)| (start, end));
+    let mut res = String::with_capacity(src.len());
+    let mut last = 0;
+    for (start, end, text) in edits {
+        res.push_str(&src[last..start]);
+        res.push_str(&text);
+        last = end;
+    }
+    res.push_str(&src[last..]);
+    res
+}
+
+// remove the items with #[cfg(test)]
+fn strip_tests(src: &str) -> String {
+    let tokens = tokenize(src);
+    let texts = tokens.iter().map(|t| t.text).collect::<Vec<_>>();
+    let mut edits = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        if texts[i..].starts_with(&["#", "[", "cfg", "(", "test", ")", "]"]) {
+            let start = tokens[i].start;
+            let mut j = i + 7;
+            let mut depth = 0;
+            while j < tokens.len() {
+                match texts[j] {
+                    "{" => depth += 1,
+                    "}" => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    ";" if depth == 0 => break,
+                    _ => {}
+                }
+                j += 1;
+            }
+            let end = tokens.get(j).map_or(src.len(), |t| t.end);
+            edits.push((start, end, String::new()));
+            i = j + 1;
+        } else {
+            i += 1;
+        }
+    }
+    apply_edits(src, edits)
+}
+
+// remove `#[macro_use] extern crate algorithms_and_data_structures_rust;`
+fn remove_extern_crate(src: &str) -> String {
+    let tokens = tokenize(src);
+    let texts = tokens.iter().map(|t| t.text).collect::<Vec<_>>();
+    let mut edits = vec![];
+    for i in 0..tokens.len() {
+        if texts[i..].starts_with(&["extern", "crate", CRATE_NAME, ";"]) {
+            let start = if i >= 4 && texts[i - 4..i] == ["#", "[", "macro_use", "]"] {
+                tokens[i - 4].start
+            } else {
+                tokens[i].start
+            };
+            edits.push((start, tokens[i + 3].end, String::new()));
+        }
+    }
+    apply_edits(src, edits)
+}
+
 fn main() {
-    println!("Hello, world!");
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (mut input, mut output) = (None, None);
+    let mut library = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" && i + 1 < args.len() {
+            output = Some(args[i + 1].clone());
+            i += 2;
+        } else if args[i] == "-l" && i + 1 < args.len() {
+            library = PathBuf::from(&args[i + 1]);
+            i += 2;
+        } else {
+            input = Some(args[i].clone());
+            i += 1;
+        }
+    }
+    let input = input.unwrap_or_else(|| {
+        eprintln!("usage: bundler <solution.rs> [-o <output.rs>] [-l <library>]");
+        process::exit(1);
+    });
+
+    let solution = fs::read_to_string(&input).unwrap_or_else(|e| {
+        eprintln!("cannot read {}: {}", input, e);
+        process::exit(1);
+    });
+    let src_dir = library.join("src");
+    if !src_dir.join("lib.rs").is_file() {
+        eprintln!("{} is not the library, specify it by -l", library.display());
+        process::exit(1);
+    }
+    let library = Library::new(&src_dir);
+    let res = library.bundle(&solution);
+    match output {
+        Some(output) => fs::write(&output, res).unwrap_or_else(|e| {
+            eprintln!("cannot write {}: {}", output, e);
+            process::exit(1);
+        }),
+        None => print!("{}", res),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> Library {
+        Library::new(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))
+    }
+
+    fn module(path: &str) -> Vec<String> {
+        path.split("::").map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn tokenize_test() {
+        let src = r##"let s = "crate::a // b"; /* x /* y */ z */ 'a' '\'' b'x' 'static r#"q"#
+        crate::io::{self, Foo}; x != y; 0..n; $crate::min!(1.5)"##;
+        let texts = tokenize(src)
+            .iter()
+            .filter(|t| t.kind != TokenKind::Punct)
+            .map(|t| t.text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "let", "s", "b", "static", "crate", "::", "io", "::", "self", "Foo", "x", "y", "n",
+                "$crate", "::", "min"
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_string_test() {
+        let texts = |src| {
+            tokenize(src)
+                .iter()
+                .filter(|t| t.kind == TokenKind::Ident)
+                .map(|t| t.text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(r####"a r##"crate::x "# b"## br###"c"### r#type d"####),
+            vec!["a", "r", "type", "d"]
+        );
+        // unterminated literals at the end of the source
+        assert_eq!(texts("x br#"), vec!["x", "br"]);
+        assert_eq!(texts("x r##\"a\"#"), vec!["x"]);
+        assert_eq!(texts("x r#"), vec!["x", "r"]);
+    }
+
+    #[test]
+    fn parse_tree_test() {
+        let tokens = tokenize("{a::{self, B as C}, d::*, e, f::{g::{h}}};");
+        let (paths, next) = parse_tree(&tokens, 0);
+        assert_eq!(
+            paths,
+            vec![
+                vec!["a"],
+                vec!["a", "B"],
+                vec!["d"],
+                vec!["e"],
+                vec!["f", "g", "h"]
+            ]
+        );
+        assert!(tokens[next].is(TokenKind::Punct, ";"));
+    }
+
+    #[test]
+    fn strip_tests_test() {
+        let src = "fn f() {}\n#[cfg(test)]\nuse x;\n#[cfg(test)]\nmod tests {\n    fn g() { let s = \"}\"; }\n}\nfn h() {}\n";
+        assert_eq!(strip_tests(src), "fn f() {}\n\n\nfn h() {}\n");
+        assert_eq!(
+            remove_extern_crate(
+                "#[macro_use]\nextern crate algorithms_and_data_structures_rust;\nfn main() {}"
+            ),
+            "\nfn main() {}"
+        );
+    }
+
+    #[test]
+    fn collect_test() {
+        let library = library();
+        assert_eq!(
+            library.macros.get("chmin"),
+            Some(&module("macros::ch_min_max"))
+        );
+        assert_eq!(library.macros.get("input"), Some(&module("macros::io")));
+
+        let solution = "use algorithms_and_data_structures_rust::data_structures::graph::Graph;\n\
+            fn main() {\n    let g = Graph::<usize>::new(3);\n    let d = g.dijkstra(0);\n}\n";
+        // dijkstra is a method of Graph implemented in algorithms::dijkstra
+        let modules = library.collect(solution);
+        assert!(modules.contains(&module("algorithms::dijkstra")));
+        assert!(modules.contains(&module("data_structures::radix_heap")));
+        assert!(!modules.contains(&module("algorithms::scc")));
+        let modules = library.collect(&solution.replace("g.dijkstra(0)", "g.size()"));
+        assert_eq!(
+            modules,
+            BTreeSet::from([module("data_structures"), module("data_structures::graph")])
+        );
+
+        let solution = "use algorithms_and_data_structures_rust::{\n    algorithms::dijkstra,\n    chmin,\n};\n";
+        let modules = library.collect(solution);
+        for m in [
+            "algorithms::dijkstra",
+            "data_structures::graph",
+            "data_structures::radix_heap",
+            "macros::ch_min_max",
+        ] {
+            assert!(modules.contains(&module(m)), "{}", m);
+        }
+        assert!(!modules.contains(&module("data_structures::segment_tree")));
+
+        // super:: paths in the library
+        let modules = library.collect(
+            "fn main() { algorithms_and_data_structures_rust::algebraic_structures::monoid::MinMonoid; }",
+        );
+        assert!(modules.contains(&module("algebraic_structures::semigroup")));
+        assert!(!modules.contains(&module("algorithms::num")));
+
+        // macros and $crate:: paths in them
+        let modules = library.collect(
+            "use algorithms_and_data_structures_rust::{input, io::scanner::Scanner};\n\
+             fn main() { input!(sc, n: usize); }",
+        );
+        assert!(modules.contains(&module("macros::io")));
+        assert!(modules.contains(&module("io::scanner")));
+    }
+
+    #[test]
+    fn remove_macro_imports_test() {
+        let library = library();
+        let src = "use algorithms_and_data_structures_rust::{\n    chmin, io::scanner::Scanner,\n    input,\n};\n\
+                   use algorithms_and_data_structures_rust::{chmax, outln};\n\
+                   pub use algorithms_and_data_structures_rust::chmin;\n\
+                   use algorithms_and_data_structures_rust::macros::ch_min_max;\n\
+                   fn main() { algorithms_and_data_structures_rust::chmin!(a, b); }\n";
+        assert_eq!(
+            library.remove_macro_imports(src),
+            "use algorithms_and_data_structures_rust::{io::scanner::Scanner};\n\n\n\
+             use algorithms_and_data_structures_rust::macros::ch_min_max;\n\
+             fn main() { algorithms_and_data_structures_rust::chmin!(a, b); }\n"
+        );
+    }
+
+    #[test]
+    fn rewrite_test() {
+        let library = library();
+        let src =
+            "use crate::{chmin, data_structures::graph};\n$crate::io::x;\npub mod a;\nmod b;\n";
+        assert_eq!(
+            library.rewrite(src),
+            "use crate::algorithms_and_data_structures_rust::{chmin, data_structures::graph};\n\
+             $crate::algorithms_and_data_structures_rust::io::x;\n\n\n"
+        );
+        assert_eq!(
+            library.rewrite("use crate::algorithms::num::gcd;"),
+            "use crate::algorithms_and_data_structures_rust::algorithms::num::gcd;"
+        );
+    }
+
+    #[test]
+    fn bundle_test() {
+        let library = library();
+        let solution = "fn main() {}\n";
+        assert_eq!(library.bundle(solution), solution);
+
+        let res = library.bundle(
+            "use algorithms_and_data_structures_rust::data_structures::union_find::UnionFind;\n",
+        );
+        assert!(res.contains("\npub mod algorithms_and_data_structures_rust {\n"));
+        assert!(res.contains("\npub mod data_structures {\npub mod union_find {\n"));
+        assert!(!res.contains("#[cfg(test)]"));
+        assert!(!res.contains("pub use crate::{"));
+    }
+
+    // bundle a solution, compile it with rustc and run it
+    #[test]
+    fn compile_test() {
+        let solution = r#"use algorithms_and_data_structures_rust::{
+    chmin, input,
+    data_structures::graph::Graph,
+    io::{scanner::{Scanner, Usize1}, writer::Writer},
+    outln,
+};
+use std::io::{stdin, stdout};
+
+fn main() {
+    let mut sc = Scanner::new(stdin().lock());
+    let mut w = Writer::new(stdout().lock());
+    input! {
+        sc,
+        n: usize,
+        m: usize,
+        edges: [(Usize1, Usize1, usize); m],
+    }
+    let mut g = Graph::new(n);
+    for &(a, b, c) in &edges {
+        g.add_undirected_edge(a, b, c);
+    }
+    let d = g.dijkstra(0);
+    let mut best = usize::MAX;
+    for &x in &d[1..] {
+        if chmin!(best, x) {
+            outln!(w, "{}", x);
+        }
+    }
+    outln!(w, "{:?}", d);
+}
+"#;
+        let dir = env::temp_dir().join(format!("bundle_compile_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (src, exe) = (dir.join("main.rs"), dir.join("main"));
+        fs::write(&src, library().bundle(solution)).unwrap();
+
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let status = process::Command::new(rustc)
+            .args(["--edition", "2021", "-o"])
+            .arg(&exe)
+            .arg(&src)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let mut child = process::Command::new(&exe)
+            .stdin(process::Stdio::piped())
+            .stdout(process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        {
+            use std::io::Write;
+            let mut stdin = child.stdin.take().unwrap();
+            stdin
+                .write_all(b"4 4\n1 2 7\n2 3 1\n1 3 5\n3 4 2\n")
+                .unwrap();
+        }
+        let output = child.wait_with_output().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            "6\n5\n[0, 6, 5, 7]\n"
+        );
+    }
 }
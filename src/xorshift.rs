@@ -0,0 +1,24 @@
+// xorshift64 pseudo random number generator for the randomized tests and the benchmarks
+pub struct XorShift {
+    seed: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> XorShift {
+        XorShift { seed }
+    }
+
+    // a random integer in [0, n)
+    pub fn rand(&mut self, n: u64) -> u64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.seed % n
+    }
+}
+
+impl Default for XorShift {
+    fn default() -> XorShift {
+        XorShift::new(88172645463325252)
+    }
+}